@@ -1,118 +1,225 @@
 import { Buffer } from "buffer";
 import { PublicKey, TransactionInstruction, Transaction } from '@solana/web3.js';
-import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, RENT_SYSVAR } from './constants';
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } from './constants';
 import BN from 'bn.js';
 
 const PROGRAM_ID = new PublicKey('5mE8RwFEnMJ1Rs4bLM2VSrzMN8RSEJkf1vXb9VpAybvi');
+const SYSTEM_PROGRAM_ID = new PublicKey("11111111111111111111111111111111");
 
 const CREATE_MEME_TOKEN_DISCRIMINATOR = Buffer.from([6, 42, 76, 101, 74, 125, 120, 59]);
 
-function serializeCreateMemeTokenArgs(name, symbol, uri, imageHash, virtualSolReserves, virtualTokenReserves) {
-  const nameBytes = Buffer.from(name, 'utf8');
-  const symbolBytes = Buffer.from(symbol, 'utf8');
-  const uriBytes = Buffer.from(uri, 'utf8');
+// Offset of Protocol.total_memes_created: discriminator, authority,
+// protocol_fee_bps, fee_schedule, three u64 settings, 4 curve presets,
+// curve_preset_count, reserve_bounds, referral_share_bps, treasury_split,
+// attester
+const TOTAL_MEMES_CREATED_OFFSET = 8 + 32 + 2 + 12 + 24 + 4 * 16 + 1 + 32 + 2 + 6 + 32;
 
-  const nameLen = Buffer.alloc(4);
-  nameLen.writeUInt32LE(nameBytes.length);
+const MAX_CURVE_SEGMENTS = 4;
 
-  const symbolLen = Buffer.alloc(4);
-  symbolLen.writeUInt32LE(symbolBytes.length);
+// All zeroes: no flip tax, allowlist, commit-reveal, auction or launch gates
+export const DEFAULT_LAUNCH_POLICY = {
+  flipTaxBps: 0,
+  flipTaxWindowSeconds: 0,
+  flipTaxDestination: 'rewardPool',
+  allowlistRoot: new Array(32).fill(0),
+  allowlistWindowSeconds: 0,
+  commitWindowSlots: 0,
+  revealWindowSlots: 0,
+  auctionWindowSlots: 0,
+  auctionTrancheBps: 0,
+  auctionMaxBidLamports: 0,
+  denylistEnabled: false,
+  minBuyerBalanceLamports: 0,
+  requireAttestation: false,
+};
 
-  const uriLen = Buffer.alloc(4);
-  uriLen.writeUInt32LE(uriBytes.length);
+function u8(value) {
+  return Buffer.from([value]);
+}
+
+function u16(value) {
+  const buffer = Buffer.alloc(2);
+  buffer.writeUInt16LE(value);
+  return buffer;
+}
+
+function u64(value) {
+  return new BN(value).toArrayLike(Buffer, 'le', 8);
+}
+
+function i64(value) {
+  return new BN(value).toTwos(64).toArrayLike(Buffer, 'le', 8);
+}
+
+function string(value) {
+  const bytes = Buffer.from(value, 'utf8');
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(bytes.length);
+  return Buffer.concat([len, bytes]);
+}
 
-  const imageHashBuffer = Buffer.from(imageHash);
+function option(value, serialize) {
+  return value == null ? u8(0) : Buffer.concat([u8(1), serialize(value)]);
+}
 
-  // Serialize virtual SOL reserves (u64)
-  const solReservesBN = new BN(virtualSolReserves);
-  const solReservesBuffer = solReservesBN.toArrayLike(Buffer, 'le', 8);
+// { preset: 0 } or { virtualSolReserves, virtualTokenReserves }
+function serializeInitialReserves(reserves) {
+  if (reserves.preset !== undefined) {
+    return Buffer.concat([u8(0), u8(reserves.preset)]);
+  }
+  return Buffer.concat([u8(1), u64(reserves.virtualSolReserves), u64(reserves.virtualTokenReserves)]);
+}
 
-  // Serialize virtual token reserves (u64)
-  const tokenReservesBN = new BN(virtualTokenReserves);
-  const tokenReservesBuffer = tokenReservesBN.toArrayLike(Buffer, 'le', 8);
+// { kind: 'constantProduct' | 'linear' | 'exponential' | 'piecewise', ... }
+function serializeCurveType(curve) {
+  switch (curve.kind) {
+    case 'constantProduct':
+      return u8(0);
+    case 'linear':
+      return Buffer.concat([u8(1), u64(curve.startPrice), u64(curve.endPrice)]);
+    case 'exponential':
+      return Buffer.concat([u8(2), u64(curve.startPrice), u8(curve.doublings)]);
+    case 'piecewise': {
+      const segments = [];
+      for (let i = 0; i < MAX_CURVE_SEGMENTS; i++) {
+        const segment = curve.segments[i] || { endBps: 0, endPrice: 0 };
+        segments.push(u16(segment.endBps), u64(segment.endPrice));
+      }
+      return Buffer.concat([u8(3), u64(curve.startPrice), ...segments, u8(curve.segments.length)]);
+    }
+    default:
+      throw new Error(`Unknown curve type ${curve.kind}`);
+  }
+}
+
+function serializeCreatorVesting(vesting) {
+  return Buffer.concat([u16(vesting.allocationBps), i64(vesting.cliffSeconds), i64(vesting.durationSeconds)]);
+}
+
+function serializeLaunchPolicy(policy) {
+  return Buffer.concat([
+    u16(policy.flipTaxBps),
+    i64(policy.flipTaxWindowSeconds),
+    u8(policy.flipTaxDestination === 'burn' ? 1 : 0),
+    Buffer.from(policy.allowlistRoot),
+    i64(policy.allowlistWindowSeconds),
+    u64(policy.commitWindowSlots),
+    u64(policy.revealWindowSlots),
+    u64(policy.auctionWindowSlots),
+    u16(policy.auctionTrancheBps),
+    u64(policy.auctionMaxBidLamports),
+    u8(policy.denylistEnabled ? 1 : 0),
+    u64(policy.minBuyerBalanceLamports),
+    u8(policy.requireAttestation ? 1 : 0),
+  ]);
+}
 
+function serializeCreateMemeTokenArgs(name, symbol, uri, imageHash, options) {
   return Buffer.concat([
     CREATE_MEME_TOKEN_DISCRIMINATOR,
-    nameLen,
-    nameBytes,
-    symbolLen,
-    symbolBytes,
-    uriLen,
-    uriBytes,
-    imageHashBuffer,
-    solReservesBuffer,
-    tokenReservesBuffer
+    string(name),
+    string(symbol),
+    string(uri),
+    Buffer.from(imageHash),
+    u64(options.perceptualHash),
+    serializeInitialReserves(options.initialReserves),
+    serializeCurveType(options.curveType),
+    option(options.creatorVesting, serializeCreatorVesting),
+    option(options.initialBuySol, u64),
+    serializeLaunchPolicy(options.launchPolicy),
   ]);
 }
 
+function findPda(seeds) {
+  return PublicKey.findProgramAddressSync(seeds, PROGRAM_ID)[0];
+}
+
+// The meme PDA is keyed by the protocol's launch counter, so it's read
+// right before building the transaction
+async function nextMemePDA(connection, protocolPDA) {
+  const protocol = await connection.getAccountInfo(protocolPDA);
+  if (!protocol) {
+    throw new Error('Protocol is not initialized');
+  }
+  const id = protocol.data.subarray(TOTAL_MEMES_CREATED_OFFSET, TOTAL_MEMES_CREATED_OFFSET + 8);
+  return findPda([Buffer.from('meme'), id]);
+}
+
 export async function createMemeTokenTransaction(
   connection,
   wallet,
   tokenName,
   tokenSymbol,
   metadataUri,
-  imageHash
+  imageHash,
+  {
+    perceptualHash = 0,
+    initialReserves = { preset: 0 },
+    curveType = { kind: 'constantProduct' },
+    creatorVesting = null,
+    initialBuySol = null,
+    launchPolicy = DEFAULT_LAUNCH_POLICY,
+  } = {}
 ) {
-  const [protocolPDA] = PublicKey.findProgramAddressSync(
-    [Buffer.from('protocol')],
-    PROGRAM_ID
-  );
-  
-  const [memePDA] = PublicKey.findProgramAddressSync(
-    [Buffer.from('meme'), wallet.publicKey.toBuffer(), Buffer.from(tokenSymbol)],
-    PROGRAM_ID
-  );
-  
-  const [mintPDA] = PublicKey.findProgramAddressSync(
-    [Buffer.from('mint'), memePDA.toBuffer()],
-    PROGRAM_ID
-  );
-  
-  const [vaultPDA] = PublicKey.findProgramAddressSync(
-    [Buffer.from('vault'), memePDA.toBuffer()],
-    PROGRAM_ID
-  );
-  
+  const protocolPDA = findPda([Buffer.from('protocol')]);
+  const memePDA = await nextMemePDA(connection, protocolPDA);
+  const mintPDA = findPda([Buffer.from('mint'), memePDA.toBuffer()]);
+
   const [creatorTokenAccount] = PublicKey.findProgramAddressSync(
     [wallet.publicKey.toBuffer(), TOKEN_PROGRAM_ID.toBuffer(), mintPDA.toBuffer()],
     ASSOCIATED_TOKEN_PROGRAM_ID
   );
-  
-  // Virtual reserves for bonding curve (Pump.fun style)
-  const virtualSolReserves = "30000000000"; // 30 SOL in lamports
-  const virtualTokenReserves = "800000000000"; // 800K tokens with 6 decimals
-
-  const data = serializeCreateMemeTokenArgs(
-    tokenName,
-    tokenSymbol,
-    metadataUri,
-    imageHash,
-    virtualSolReserves,
-    virtualTokenReserves
-  );
-  
+
+  const data = serializeCreateMemeTokenArgs(tokenName, tokenSymbol, metadataUri, imageHash, {
+    perceptualHash,
+    initialReserves,
+    curveType,
+    creatorVesting,
+    initialBuySol,
+    launchPolicy,
+  });
+
+  // Optional accounts that aren't used are passed as the program id
+  const optional = (used, seeds) => ({
+    pubkey: used ? findPda(seeds) : PROGRAM_ID,
+    isSigner: false,
+    isWritable: used,
+  });
+  const batched = launchPolicy.commitWindowSlots > 0 || launchPolicy.auctionWindowSlots > 0;
+  const meme = memePDA.toBuffer();
+
   const keys = [
     { pubkey: protocolPDA, isSigner: false, isWritable: true },
     { pubkey: memePDA, isSigner: false, isWritable: true },
+    { pubkey: findPda([Buffer.from('image'), Buffer.from(imageHash)]), isSigner: false, isWritable: true },
     { pubkey: mintPDA, isSigner: false, isWritable: true },
     { pubkey: creatorTokenAccount, isSigner: false, isWritable: true },
-    { pubkey: vaultPDA, isSigner: false, isWritable: true },
+    { pubkey: findPda([Buffer.from('token_vault'), meme]), isSigner: false, isWritable: true },
+    optional(creatorVesting != null, [Buffer.from('vesting'), meme]),
+    optional(creatorVesting != null, [Buffer.from('vesting_vault'), meme]),
+    optional(initialBuySol != null, [Buffer.from('wallet'), meme, wallet.publicKey.toBuffer()]),
+    { pubkey: findPda([Buffer.from('vault'), meme]), isSigner: false, isWritable: true },
+    { pubkey: findPda([Buffer.from('holders'), meme]), isSigner: false, isWritable: true },
+    { pubkey: findPda([Buffer.from('reward_pool'), meme]), isSigner: false, isWritable: true },
+    { pubkey: findPda([Buffer.from('oracle'), meme]), isSigner: false, isWritable: true },
+    optional(batched, [Buffer.from('batch'), meme]),
+    optional(launchPolicy.denylistEnabled, [Buffer.from('denylist'), meme]),
     { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
+    { pubkey: findPda([Buffer.from('treasury')]), isSigner: false, isWritable: true },
     { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
     { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-    { pubkey: new PublicKey("11111111111111111111111111111111"), isSigner: false, isWritable: false },
-    { pubkey: RENT_SYSVAR, isSigner: false, isWritable: false },
+    { pubkey: SYSTEM_PROGRAM_ID, isSigner: false, isWritable: false },
   ];
-  
+
   const instruction = new TransactionInstruction({
     keys,
     programId: PROGRAM_ID,
     data,
   });
-  
+
   const transaction = new Transaction().add(instruction);
   transaction.feePayer = wallet.publicKey;
   transaction.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
-  
-  return { transaction, mintPDA };
+
+  return { transaction, mintPDA, memePDA };
 }
@@ -0,0 +1,37 @@
+// ============================================================================
+// Launch Allowlist
+// ============================================================================
+//
+// A launch can open to an allowlist before the public. The creator commits
+// the root of a Merkle tree whose leaves are (wallet, cap) pairs, and during
+// the allowlist window every buy carries the buyer's cap and a proof of its
+// leaf. Pairs are hashed in sorted order so proofs need no left/right flags,
+// and leaves and inner nodes are prefixed differently so neither can be
+// passed off as the other.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+// Enough for a million wallets, and keeps buy instructions well under the
+// transaction size limit
+pub const MAX_PROOF_LENGTH: usize = 20;
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Leaf for `wallet` allowed to spend up to `cap_lamports` on the curve.
+pub fn leaf(wallet: &Pubkey, cap_lamports: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, wallet.as_ref(), &cap_lamports.to_le_bytes()]).to_bytes()
+}
+
+/// Parent of two nodes, independent of their order.
+pub fn parent(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, lo, hi]).to_bytes()
+}
+
+/// Whether `proof` connects `leaf` to `root`.
+pub fn verify(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    proof.len() <= MAX_PROOF_LENGTH
+        && proof.iter().fold(leaf, |node, sibling| parent(&node, sibling)) == *root
+}
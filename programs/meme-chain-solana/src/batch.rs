@@ -0,0 +1,87 @@
+// ============================================================================
+// Launch Batches
+// ============================================================================
+//
+// Launch orders that fill together instead of first come, first served.
+// Orders escrow SOL during a launch window, the whole batch is bought on the
+// curve in one go, and each order then claims its pro-rata share of the
+// tokens at the batch's single clearing price. Costs round up and token
+// shares round down, so the claims never add up to more than the fill.
+//
+// Orders either come from commit-reveal buys, whose sizes stay hidden until
+// the commit window closes, or from open auction bids.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::curve::{self, BuyQuote};
+use crate::ErrorCode;
+
+/// Commitment to a hidden order of `amount` lamports. Binding the buyer
+/// stops anyone from replaying someone else's commitment as their own.
+pub fn commitment(buyer: &Pubkey, amount: u64, salt: &[u8; 32]) -> [u8; 32] {
+    hashv(&[buyer.as_ref(), &amount.to_le_bytes(), salt]).to_bytes()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OrderFill {
+    pub tokens: u64, // Share of the tokens the batch bought
+    pub cost: u64,   // Share of the SOL the batch spent
+    pub refund: u64, // Rest of the order, left over when the curve ran out
+}
+
+/// Fill for an order of `amount` in a batch of `total_amount`, of which
+/// `sol_filled` bought `tokens_filled` tokens on the curve.
+pub fn fill_order(amount: u64, total_amount: u64, sol_filled: u64, tokens_filled: u64) -> Result<OrderFill> {
+    require!(
+        amount <= total_amount && sol_filled <= total_amount,
+        ErrorCode::InvalidAmount
+    );
+    if amount == 0 {
+        return Ok(OrderFill { tokens: 0, cost: 0, refund: 0 });
+    }
+    let tokens = (amount as u128) * tokens_filled as u128 / total_amount as u128;
+    let cost = ((amount as u128) * sol_filled as u128).div_ceil(total_amount as u128);
+    Ok(OrderFill {
+        tokens: tokens as u64,
+        cost: cost as u64,
+        refund: amount - cost as u64,
+    })
+}
+
+/// Splits `fill` at `max_tokens`, for orders that would take a wallet over
+/// its cap. Returns the capped fill and the excess, whose tokens go back to
+/// the curve at the order's own price. The excess cost rounds down, so the
+/// curve never pays back more than the order spent.
+pub fn cap_fill(fill: OrderFill, max_tokens: u64) -> (OrderFill, OrderFill) {
+    if fill.tokens <= max_tokens {
+        return (fill, OrderFill { tokens: 0, cost: 0, refund: 0 });
+    }
+    let tokens = fill.tokens - max_tokens;
+    let cost = ((fill.cost as u128) * tokens as u128 / fill.tokens as u128) as u64;
+    (
+        OrderFill { tokens: max_tokens, cost: fill.cost - cost, refund: fill.refund },
+        OrderFill { tokens, cost, refund: 0 },
+    )
+}
+
+/// Fill for an auction of `tranche` tokens on a constant product curve at
+/// (`virtual_sol`, `virtual_token`) with `total_bids` lamports bid. When the
+/// bids cover what the curve would charge for the whole tranche, all of it
+/// sells at the clearing price `total_bids / tranche` and the virtual SOL
+/// reserve is reset so the curve carries on from that price. Otherwise the
+/// bids fill as one curve buy at the curve's own prices.
+pub fn clear_auction(virtual_sol: u64, virtual_token: u64, total_bids: u64, tranche: u64) -> Result<BuyQuote> {
+    require!(total_bids > 0 && tranche > 0, ErrorCode::InvalidAmount);
+    let quote = curve::quote_buy(virtual_sol, virtual_token, total_bids, tranche)?;
+    if quote.tokens_out < tranche {
+        return Ok(quote);
+    }
+    let new_virtual_sol = (total_bids as u128) * quote.new_virtual_token as u128 / tranche as u128;
+    Ok(BuyQuote {
+        sol_in: total_bids,
+        tokens_out: tranche,
+        new_virtual_sol: u64::try_from(new_virtual_sol).map_err(|_| error!(ErrorCode::Overflow))?,
+        new_virtual_token: quote.new_virtual_token,
+    })
+}
@@ -0,0 +1,605 @@
+// ============================================================================
+// Bonding Curve Math
+// ============================================================================
+//
+// Pure integer math shared by the trading instructions. Nothing in here
+// touches accounts, so every function can be exercised directly from tests.
+
+use std::ops::RangeInclusive;
+
+use anchor_lang::prelude::*;
+
+use crate::ErrorCode;
+
+pub const BASIS_POINTS: u64 = 10_000;
+
+// Supply split: 80% is sold through the curve, 20% is held back as the
+// migration reserve that seeds AMM liquidity when the token graduates
+pub const BONDING_CURVE_SUPPLY_BPS: u64 = 8_000;
+
+/// Splits `total_supply` into `(bonding_curve_supply, migration_reserve)`.
+pub fn split_supply(total_supply: u64) -> Result<(u64, u64)> {
+    let curve_supply = (total_supply as u128)
+        .checked_mul(BONDING_CURVE_SUPPLY_BPS as u128)
+        .ok_or(ErrorCode::Overflow)?
+        .checked_div(BASIS_POINTS as u128)
+        .ok_or(ErrorCode::Overflow)? as u64;
+    let migration_reserve = total_supply
+        .checked_sub(curve_supply)
+        .ok_or(ErrorCode::Overflow)?;
+    Ok((curve_supply, migration_reserve))
+}
+
+/// Tokens still available for sale from the curve allocation.
+pub fn remaining_curve_supply(bonding_curve_supply: u64, circulating_supply: u64) -> u64 {
+    bonding_curve_supply.saturating_sub(circulating_supply)
+}
+
+/// Supply cap invariant: tokens in circulation plus everything reserved
+/// outside the curve (migration reserve, creator allocation) can never exceed
+/// the fixed total supply.
+pub fn supply_within_cap(total_supply: u64, circulating_supply: u64, reserved: u64) -> bool {
+    circulating_supply
+        .checked_add(reserved)
+        .is_some_and(|committed| committed <= total_supply)
+}
+
+fn div_ceil(numerator: u128, denominator: u128) -> Result<u128> {
+    require!(denominator > 0, ErrorCode::InvalidReserves);
+    Ok(numerator.div_ceil(denominator))
+}
+
+/// Tokens received for `sol_in` on the constant product curve (x * y = k).
+/// The new token reserve is rounded up so rounding always favours the curve.
+pub fn tokens_out_for_sol(virtual_sol: u64, virtual_token: u64, sol_in: u64) -> Result<u64> {
+    let k = (virtual_sol as u128)
+        .checked_mul(virtual_token as u128)
+        .ok_or(ErrorCode::Overflow)?;
+    let new_sol = (virtual_sol as u128)
+        .checked_add(sol_in as u128)
+        .ok_or(ErrorCode::Overflow)?;
+    let new_token = div_ceil(k, new_sol)?;
+    let tokens_out = (virtual_token as u128)
+        .checked_sub(new_token)
+        .ok_or(ErrorCode::InvalidAmount)?;
+    Ok(tokens_out as u64)
+}
+
+/// SOL that must be paid to take exactly `tokens_out` off the curve, rounded up.
+pub fn sol_in_for_tokens(virtual_sol: u64, virtual_token: u64, tokens_out: u64) -> Result<u64> {
+    require!(tokens_out < virtual_token, ErrorCode::InvalidAmount);
+    let k = (virtual_sol as u128)
+        .checked_mul(virtual_token as u128)
+        .ok_or(ErrorCode::Overflow)?;
+    let new_token = (virtual_token - tokens_out) as u128;
+    let new_sol = div_ceil(k, new_token)?;
+    let sol_in = new_sol
+        .checked_sub(virtual_sol as u128)
+        .ok_or(ErrorCode::Overflow)?;
+    u64::try_from(sol_in).map_err(|_| error!(ErrorCode::Overflow))
+}
+
+/// SOL returned for selling `tokens_in` back into the curve. The new SOL
+/// reserve is rounded up so rounding always favours the curve.
+pub fn sol_out_for_tokens(virtual_sol: u64, virtual_token: u64, tokens_in: u64) -> Result<u64> {
+    let k = (virtual_sol as u128)
+        .checked_mul(virtual_token as u128)
+        .ok_or(ErrorCode::Overflow)?;
+    let new_token = (virtual_token as u128)
+        .checked_add(tokens_in as u128)
+        .ok_or(ErrorCode::Overflow)?;
+    let new_sol = div_ceil(k, new_token)?;
+    let sol_out = (virtual_sol as u128)
+        .checked_sub(new_sol)
+        .ok_or(ErrorCode::InvalidAmount)?;
+    Ok(sol_out as u64)
+}
+
+/// `amount * fee_bps / 10_000`, rounded down.
+pub fn fee_amount(amount: u64, fee_bps: u16) -> Result<u64> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(ErrorCode::Overflow)?
+        / BASIS_POINTS as u128;
+    Ok(fee as u64)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TradeFees {
+    pub protocol_fee: u64,
+    pub creator_fee: u64,
+    pub referral_fee: u64, // Carved out of the protocol fee
+}
+
+impl TradeFees {
+    pub fn total(&self) -> Result<u64> {
+        Ok(self.protocol_fee
+            .checked_add(self.creator_fee)
+            .and_then(|total| total.checked_add(self.referral_fee))
+            .ok_or(ErrorCode::Overflow)?)
+    }
+
+    /// Moves `share_bps` of the protocol fee to the referrer. The total is unchanged.
+    pub fn with_referral(self, share_bps: u16) -> Result<TradeFees> {
+        let referral_fee = fee_amount(self.protocol_fee, share_bps)?;
+        Ok(TradeFees {
+            protocol_fee: self.protocol_fee - referral_fee,
+            referral_fee,
+            ..self
+        })
+    }
+}
+
+/// Protocol fee that decays linearly from `start_bps` when buys open to
+/// `end_bps` once `decay_slots` slots have passed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug, InitSpace)]
+pub struct FeeSchedule {
+    pub start_bps: u16,
+    pub end_bps: u16,
+    pub decay_slots: u64,
+}
+
+/// Protocol fee in bps for a trade `slots_since_launch` slots after buys open.
+pub fn effective_fee_bps(schedule: &FeeSchedule, slots_since_launch: u64) -> u16 {
+    if slots_since_launch >= schedule.decay_slots || schedule.start_bps <= schedule.end_bps {
+        return schedule.end_bps;
+    }
+    let spread = (schedule.start_bps - schedule.end_bps) as u128;
+    let remaining = (schedule.decay_slots - slots_since_launch) as u128;
+    // Rounded up, so the fee never undershoots the schedule
+    let decayed = (spread * remaining).div_ceil(schedule.decay_slots as u128) as u16;
+    schedule.end_bps + decayed
+}
+
+/// Fees owed on a trade of `amount` lamports through the curve.
+pub fn trade_fees(amount: u64, protocol_fee_bps: u16, creator_fee_bps: u16) -> Result<TradeFees> {
+    Ok(TradeFees {
+        protocol_fee: fee_amount(amount, protocol_fee_bps)?,
+        creator_fee: fee_amount(amount, creator_fee_bps)?,
+        referral_fee: 0,
+    })
+}
+
+/// Early-flip sell surcharge: `tax_bps` of `sol_out` when the wallet's first
+/// buy was less than `window_seconds` ago. Wallets that never bought
+/// (`first_buy_at == 0`) got their tokens by transfer and are measured from
+/// `created_at`, so moving tokens to a fresh wallet doesn't dodge the tax.
+pub fn flip_tax(
+    sol_out: u64,
+    tax_bps: u16,
+    window_seconds: i64,
+    first_buy_at: i64,
+    created_at: i64,
+    now: i64,
+) -> Result<u64> {
+    let held_since = if first_buy_at == 0 { created_at } else { first_buy_at };
+    if tax_bps == 0 || now.saturating_sub(held_since) >= window_seconds {
+        return Ok(0);
+    }
+    fee_amount(sol_out, tax_bps)
+}
+
+/// Lamports the SOL vault must hold: its rent-exempt minimum plus every
+/// lamport the curve has taken in and not yet paid out.
+pub fn expected_vault_lamports(rent_exempt_minimum: u64, real_sol_reserves: u64) -> Result<u64> {
+    Ok(rent_exempt_minimum
+        .checked_add(real_sol_reserves)
+        .ok_or(ErrorCode::Overflow)?)
+}
+
+/// Vault balance left after paying out `amount`. Fails with `VaultUnderfunded`
+/// instead of underflowing or leaving the vault below its rent-exempt minimum.
+pub fn vault_balance_after_withdrawal(
+    vault_lamports: u64,
+    amount: u64,
+    rent_exempt_minimum: u64,
+) -> Result<u64> {
+    let remaining = vault_lamports
+        .checked_sub(amount)
+        .ok_or(ErrorCode::VaultUnderfunded)?;
+    require!(remaining >= rent_exempt_minimum, ErrorCode::VaultUnderfunded);
+    Ok(remaining)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BuyQuote {
+    /// SOL actually consumed by the curve (less than requested when clamped)
+    pub sol_in: u64,
+    pub tokens_out: u64,
+    pub new_virtual_sol: u64,
+    pub new_virtual_token: u64,
+}
+
+/// Quotes a buy of up to `sol_amount`, clamped so the curve never sells more
+/// than `remaining_supply` tokens. When the clamp kicks in only the SOL needed
+/// for the remaining tokens is consumed.
+pub fn quote_buy(
+    virtual_sol: u64,
+    virtual_token: u64,
+    sol_amount: u64,
+    remaining_supply: u64,
+) -> Result<BuyQuote> {
+    let mut sol_in = sol_amount;
+    let mut tokens_out = tokens_out_for_sol(virtual_sol, virtual_token, sol_amount)?;
+
+    if tokens_out > remaining_supply {
+        tokens_out = remaining_supply;
+        sol_in = sol_in_for_tokens(virtual_sol, virtual_token, tokens_out)?;
+    }
+
+    Ok(BuyQuote {
+        sol_in,
+        tokens_out,
+        new_virtual_sol: virtual_sol.checked_add(sol_in).ok_or(ErrorCode::Overflow)?,
+        new_virtual_token: virtual_token - tokens_out,
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SellQuote {
+    pub sol_out: u64,
+    pub new_virtual_sol: u64,
+    pub new_virtual_token: u64,
+}
+
+pub fn quote_sell(virtual_sol: u64, virtual_token: u64, tokens_in: u64) -> Result<SellQuote> {
+    let sol_out = sol_out_for_tokens(virtual_sol, virtual_token, tokens_in)?;
+    Ok(SellQuote {
+        sol_out,
+        new_virtual_sol: virtual_sol - sol_out,
+        new_virtual_token: virtual_token.checked_add(tokens_in).ok_or(ErrorCode::Overflow)?,
+    })
+}
+
+// ============================================================================
+// Curve Shapes
+// ============================================================================
+//
+// Besides the constant product curve, which prices off virtual reserves, a
+// meme can launch on a curve whose price depends only on how many tokens
+// have been sold. Each shape is defined by its cumulative cost C(sold) in
+// lamports: a buy of `n` tokens costs C(sold + n) - C(sold) and a sell of `n`
+// returns C(sold) - C(sold - n). Trades telescope, so whatever path they take
+// the curve holds exactly C(sold) and can always pay every seller back.
+//
+// Shape prices are in nanolamports per whole token (10^6 base units).
+
+pub const MAX_CURVE_SEGMENTS: usize = 4;
+pub const MAX_EXPONENTIAL_DOUBLINGS: u8 = 10;
+
+// Nanolamports per lamport * base units per whole token
+const COST_DENOMINATOR: u128 = 1_000_000_000 * 1_000_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug, InitSpace)]
+pub struct CurveSegment {
+    /// End of the segment, in bps of the bonding curve supply
+    pub end_bps: u16,
+    /// Price at the end of the segment
+    pub end_price: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum CurveType {
+    /// x * y = k over the meme's virtual reserves
+    ConstantProduct,
+    /// Price rises linearly from `start_price` to `end_price` across the curve supply
+    Linear { start_price: u64, end_price: u64 },
+    /// Price starts at `start_price` and doubles `doublings` times across the curve supply
+    Exponential { start_price: u64, doublings: u8 },
+    /// Linear between breakpoints; only the first `segment_count` segments are used
+    Piecewise {
+        start_price: u64,
+        segments: [CurveSegment; MAX_CURVE_SEGMENTS],
+        segment_count: u8,
+    },
+}
+
+/// Checks a curve's parameters at creation. Every shape must have a positive
+/// starting price that never decreases.
+pub fn validate_curve_type(curve_type: &CurveType) -> Result<()> {
+    match *curve_type {
+        CurveType::ConstantProduct => {}
+        CurveType::Linear { start_price, end_price } => {
+            require!(start_price > 0 && end_price >= start_price, ErrorCode::InvalidCurve);
+        }
+        CurveType::Exponential { start_price, doublings } => {
+            require!(
+                start_price > 0 && (1..=MAX_EXPONENTIAL_DOUBLINGS).contains(&doublings),
+                ErrorCode::InvalidCurve
+            );
+        }
+        CurveType::Piecewise { start_price, segments, segment_count } => {
+            let count = segment_count as usize;
+            require!(
+                start_price > 0 && (1..=MAX_CURVE_SEGMENTS).contains(&count),
+                ErrorCode::InvalidCurve
+            );
+            let (mut prev_bps, mut prev_price) = (0u16, start_price);
+            for segment in &segments[..count] {
+                require!(
+                    segment.end_bps > prev_bps && segment.end_price >= prev_price,
+                    ErrorCode::InvalidCurve
+                );
+                prev_bps = segment.end_bps;
+                prev_price = segment.end_price;
+            }
+            require!(prev_bps as u64 == BASIS_POINTS, ErrorCode::InvalidCurve);
+        }
+    }
+    Ok(())
+}
+
+/// Checks a shaped curve against the protocol's limits at creation: it has
+/// to start inside `start_prices`, and buying out all `curve_supply` tokens
+/// has to cost an amount of lamports that fits in a u64.
+pub fn validate_curve_bounds(
+    curve_type: &CurveType,
+    curve_supply: u64,
+    start_prices: RangeInclusive<u64>,
+) -> Result<()> {
+    let start_price = match *curve_type {
+        CurveType::ConstantProduct => return Ok(()),
+        CurveType::Linear { start_price, .. }
+        | CurveType::Exponential { start_price, .. }
+        | CurveType::Piecewise { start_price, .. } => start_price,
+    };
+    require!(start_prices.contains(&start_price), ErrorCode::ReservesOutOfBounds);
+    curve_cost(curve_type, curve_supply, curve_supply).map_err(|_| error!(ErrorCode::InvalidCurve))?;
+    Ok(())
+}
+
+/// Constant product price at (`virtual_sol`, `virtual_token`), in the same
+/// nanolamports per whole token that shapes are priced in.
+pub fn reserve_price(virtual_sol: u64, virtual_token: u64) -> u64 {
+    let price = (virtual_sol as u128) * COST_DENOMINATOR / virtual_token.max(1) as u128;
+    price.min(u64::MAX as u128) as u64
+}
+
+/// Cumulative cost C(sold) in lamports of a shaped curve over `curve_supply`
+/// tokens. Rounded down, and never decreasing in `sold`.
+pub fn curve_cost(curve_type: &CurveType, curve_supply: u64, sold: u64) -> Result<u64> {
+    require!(curve_supply > 0, ErrorCode::InvalidReserves);
+    require!(sold <= curve_supply, ErrorCode::CurveSupplyExceeded);
+
+    let scaled = match *curve_type {
+        CurveType::ConstantProduct => return err!(ErrorCode::InvalidCurve),
+        CurveType::Linear { start_price, end_price } => {
+            segment_cost(start_price, end_price, curve_supply, sold)?
+        }
+        CurveType::Exponential { start_price, doublings } => {
+            exponential_cost(start_price, doublings, curve_supply, sold)?
+        }
+        CurveType::Piecewise { start_price, segments, segment_count } => {
+            let mut cost = 0u128;
+            let (mut seg_start, mut seg_price) = (0u64, start_price);
+            for segment in &segments[..segment_count as usize] {
+                let seg_end = ((curve_supply as u128) * segment.end_bps as u128
+                    / BASIS_POINTS as u128) as u64;
+                let width = seg_end.saturating_sub(seg_start);
+                let filled = sold.saturating_sub(seg_start).min(width);
+                cost = cost
+                    .checked_add(segment_cost(seg_price, segment.end_price, width, filled)?)
+                    .ok_or(ErrorCode::Overflow)?;
+                if sold <= seg_end {
+                    break;
+                }
+                seg_start = seg_end;
+                seg_price = segment.end_price;
+            }
+            cost
+        }
+    };
+    u64::try_from(scaled / COST_DENOMINATOR).map_err(|_| error!(ErrorCode::Overflow))
+}
+
+/// Lamports a buy of `tokens` costs at `sold`.
+pub fn buy_cost(curve_type: &CurveType, curve_supply: u64, sold: u64, tokens: u64) -> Result<u64> {
+    let after = sold.checked_add(tokens).ok_or(ErrorCode::Overflow)?;
+    Ok(curve_cost(curve_type, curve_supply, after)? - curve_cost(curve_type, curve_supply, sold)?)
+}
+
+/// Lamports a sell of `tokens` returns at `sold`.
+pub fn sell_proceeds(curve_type: &CurveType, curve_supply: u64, sold: u64, tokens: u64) -> Result<u64> {
+    let after = sold.checked_sub(tokens).ok_or(ErrorCode::InsufficientFunds)?;
+    Ok(curve_cost(curve_type, curve_supply, sold)? - curve_cost(curve_type, curve_supply, after)?)
+}
+
+/// Inverse of `buy_cost`: the most tokens, up to `max_tokens`, that
+/// `sol_amount` pays for at `sold`.
+pub fn tokens_for_sol(
+    curve_type: &CurveType,
+    curve_supply: u64,
+    sold: u64,
+    sol_amount: u64,
+    max_tokens: u64,
+) -> Result<u64> {
+    let budget = curve_cost(curve_type, curve_supply, sold)?.saturating_add(sol_amount);
+    let (mut lo, mut hi) = (0u64, max_tokens);
+    while lo < hi {
+        let mid = lo + (hi - lo).div_ceil(2);
+        if curve_cost(curve_type, curve_supply, sold + mid)? <= budget {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    Ok(lo)
+}
+
+// Scaled cost of the first `filled` tokens of a linear segment `width` wide.
+// Both terms are non-decreasing in `filled` so flooring keeps C monotone.
+fn segment_cost(start_price: u64, end_price: u64, width: u64, filled: u64) -> Result<u128> {
+    if filled == 0 {
+        return Ok(0);
+    }
+    let flat = (start_price as u128)
+        .checked_mul(filled as u128)
+        .ok_or(ErrorCode::Overflow)?;
+    let rise = ((end_price - start_price) as u128)
+        .checked_mul(filled as u128)
+        .ok_or(ErrorCode::Overflow)?
+        / (2 * width as u128);
+    let rise = rise
+        .checked_mul(filled as u128)
+        .ok_or(ErrorCode::Overflow)?;
+    Ok(flat.checked_add(rise).ok_or(ErrorCode::Overflow)?)
+}
+
+// Fixed point for the exponential curve: 2^x is evaluated with a 52-bit
+// fractional exponent and a 62-bit mantissa, leaving enough headroom that
+// rounding can never make C step backwards between adjacent `sold` values.
+const EXP_FRACTION_BITS: u32 = 52;
+const ONE_Q62: u128 = 1 << 62;
+const LN2_Q62: u128 = 3_196_577_161_300_663_915;
+
+// 2^(2^-i) in Q62 for i = 1..=52
+const EXP2_TABLE: [u64; EXP_FRACTION_BITS as usize] = [
+    6521908912666391106, 5484249825272419512, 5029079263719320435,
+    4815862801830788490, 4712668792719003884, 4661903986662671290,
+    4636727017470743990, 4624189567668517720, 4617933561212708776,
+    4614808732577250068, 4613247111281068008, 4612466498810092975,
+    4612076242109103707, 4611881126141011236, 4611783571252412754,
+    4611734794581956353, 4611710406440186476, 4611698212417665819,
+    4611692115418496524, 4611689066921934630, 4611687542674409371,
+    4611686780550835664, 4611686399489096040, 4611686208958238036,
+    4611686113692811986, 4611686066060099699, 4611686042243743740,
+    4611686030335565807, 4611686024381476851, 4611686021404432377,
+    4611686019915910140, 4611686019171649022, 4611686018799518463,
+    4611686018613453183, 4611686018520420544, 4611686018473904224,
+    4611686018450646064, 4611686018439016984, 4611686018433202444,
+    4611686018430295174, 4611686018428841539, 4611686018428114721,
+    4611686018427751313, 4611686018427569608, 4611686018427478756,
+    4611686018427433330, 4611686018427410617, 4611686018427399261,
+    4611686018427393582, 4611686018427390743, 4611686018427389324,
+    4611686018427388614,
+];
+
+// 2^x in Q62 for an exponent with EXP_FRACTION_BITS fractional bits
+fn exp2_q62(exponent: u128) -> Result<u128> {
+    let whole = (exponent >> EXP_FRACTION_BITS) as u32;
+    let mut value = ONE_Q62;
+    for (i, factor) in EXP2_TABLE.iter().enumerate() {
+        if exponent & (1 << (EXP_FRACTION_BITS - 1 - i as u32)) != 0 {
+            value = (value * *factor as u128) >> 62;
+        }
+    }
+    value.checked_shl(whole).ok_or(error!(ErrorCode::Overflow))
+}
+
+// Scaled integral of P0 * 2^(x * d / S) from 0 to `sold`:
+// P0 * S * (2^(sold * d / S) - 1) / (d * ln 2)
+fn exponential_cost(start_price: u64, doublings: u8, curve_supply: u64, sold: u64) -> Result<u128> {
+    let exponent = ((sold as u128) * doublings as u128)
+        .checked_shl(EXP_FRACTION_BITS)
+        .ok_or(ErrorCode::Overflow)?
+        / curve_supply as u128;
+    let growth = exp2_q62(exponent)? - ONE_Q62;
+    let integral = (curve_supply as u128)
+        .checked_mul(growth)
+        .ok_or(ErrorCode::Overflow)?
+        / LN2_Q62;
+    Ok((start_price as u128)
+        .checked_mul(integral)
+        .ok_or(ErrorCode::Overflow)?
+        / doublings as u128)
+}
+
+/// Where a meme currently sits on its curve.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CurvePosition {
+    pub curve_type: CurveType,
+    pub curve_supply: u64,
+    pub sold: u64,
+    pub virtual_sol: u64,
+    pub virtual_token: u64,
+}
+
+/// `quote_buy` for any curve type. Shaped curves leave the virtual reserves
+/// untouched and only charge what the tokens cost.
+pub fn quote_buy_on(position: &CurvePosition, sol_amount: u64, remaining_supply: u64) -> Result<BuyQuote> {
+    let CurvePosition { curve_type, curve_supply, sold, virtual_sol, virtual_token } = *position;
+    if curve_type == CurveType::ConstantProduct {
+        return quote_buy(virtual_sol, virtual_token, sol_amount, remaining_supply);
+    }
+
+    let tokens_out = tokens_for_sol(&curve_type, curve_supply, sold, sol_amount, remaining_supply)?;
+    let sol_in = buy_cost(&curve_type, curve_supply, sold, tokens_out)?;
+    require!(sol_in > 0, ErrorCode::InvalidAmount);
+    Ok(BuyQuote {
+        sol_in,
+        tokens_out,
+        new_virtual_sol: virtual_sol,
+        new_virtual_token: virtual_token,
+    })
+}
+
+/// `quote_sell` for any curve type.
+pub fn quote_sell_on(position: &CurvePosition, tokens_in: u64) -> Result<SellQuote> {
+    let CurvePosition { curve_type, curve_supply, sold, virtual_sol, virtual_token } = *position;
+    if curve_type == CurveType::ConstantProduct {
+        return quote_sell(virtual_sol, virtual_token, tokens_in);
+    }
+
+    Ok(SellQuote {
+        sol_out: sell_proceeds(&curve_type, curve_supply, sold, tokens_in)?,
+        new_virtual_sol: virtual_sol,
+        new_virtual_token: virtual_token,
+    })
+}
+
+/// Marginal price at `position`, in nanolamports per whole token. Saturates
+/// rather than failing so a price readout can never block a trade.
+pub fn spot_price(position: &CurvePosition) -> Result<u64> {
+    let CurvePosition { curve_type, curve_supply, sold, virtual_sol, virtual_token } = *position;
+    let price: u128 = match curve_type {
+        CurveType::ConstantProduct => {
+            require!(virtual_token > 0, ErrorCode::InvalidReserves);
+            (virtual_sol as u128) * COST_DENOMINATOR / virtual_token as u128
+        }
+        CurveType::Linear { start_price, end_price } => {
+            interpolate(start_price, end_price, curve_supply, sold)
+        }
+        CurveType::Exponential { start_price, doublings } => {
+            require!(curve_supply > 0, ErrorCode::InvalidReserves);
+            let exponent = ((sold as u128) * doublings as u128)
+                .checked_shl(EXP_FRACTION_BITS)
+                .ok_or(ErrorCode::Overflow)?
+                / curve_supply as u128;
+            (start_price as u128).saturating_mul(exp2_q62(exponent)?) >> 62
+        }
+        CurveType::Piecewise { start_price, segments, segment_count } => {
+            let (mut seg_start, mut seg_price) = (0u64, start_price);
+            let mut price = start_price as u128;
+            for segment in &segments[..segment_count as usize] {
+                let seg_end = ((curve_supply as u128) * segment.end_bps as u128
+                    / BASIS_POINTS as u128) as u64;
+                price = interpolate(seg_price, segment.end_price, seg_end - seg_start, sold - seg_start);
+                if sold <= seg_end {
+                    break;
+                }
+                seg_start = seg_end;
+                seg_price = segment.end_price;
+            }
+            price
+        }
+    };
+    Ok(price.min(u64::MAX as u128) as u64)
+}
+
+// Price `filled` tokens into a linear segment `width` wide
+fn interpolate(start_price: u64, end_price: u64, width: u64, filled: u64) -> u128 {
+    if width == 0 {
+        return end_price as u128;
+    }
+    let rise = (end_price - start_price) as u128 * filled.min(width) as u128 / width as u128;
+    start_price as u128 + rise
+}
+
+/// How far a trade moves the spot price, in bps of the price before it.
+pub fn price_impact_bps(price_before: u64, price_after: u64) -> u32 {
+    if price_before == 0 {
+        return 0;
+    }
+    let bps = (price_before.abs_diff(price_after) as u128) * BASIS_POINTS as u128 / price_before as u128;
+    bps.min(u32::MAX as u128) as u32
+}
@@ -0,0 +1,48 @@
+// ============================================================================
+// Holder Tracking
+// ============================================================================
+//
+// Leaderboard of the largest curve traders for each meme. Balances are the
+// ones seen at each wallet's last trade, so the frontend can show holder
+// concentration without scanning every token account.
+
+use anchor_lang::prelude::*;
+
+use crate::curve::BASIS_POINTS;
+
+pub const TOP_HOLDERS: usize = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug, InitSpace)]
+pub struct TopHolder {
+    pub wallet: Pubkey,
+    pub balance: u64,
+}
+
+/// Records `wallet`'s latest balance in `top`, which stays sorted largest
+/// first. A zero balance drops the wallet; empty slots are zeroed. On ties
+/// the wallet already on the board keeps its place.
+pub fn record_balance(top: &mut [TopHolder; TOP_HOLDERS], wallet: Pubkey, balance: u64) {
+    let mut entries: Vec<TopHolder> = top
+        .iter()
+        .copied()
+        .filter(|h| h.balance > 0 && h.wallet != wallet)
+        .collect();
+    if balance > 0 {
+        entries.push(TopHolder { wallet, balance });
+    }
+    entries.sort_by_key(|h| std::cmp::Reverse(h.balance));
+    entries.truncate(TOP_HOLDERS);
+
+    *top = [TopHolder::default(); TOP_HOLDERS];
+    top[..entries.len()].copy_from_slice(&entries);
+}
+
+/// Share of `total_supply` held by the first `n` entries of `top`, in bps.
+pub fn concentration_bps(top: &[TopHolder], n: usize, total_supply: u64) -> u16 {
+    if total_supply == 0 {
+        return 0;
+    }
+    let held: u128 = top.iter().take(n).map(|h| h.balance as u128).sum();
+    let bps = held * BASIS_POINTS as u128 / total_supply as u128;
+    bps.min(BASIS_POINTS as u128) as u16
+}
@@ -1,5 +1,3836 @@
+// Anchor 0.31 generated IDL handlers still call the deprecated AccountInfo::realloc
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, MintTo, Burn};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, MintTo, SetAuthority, Transfer};
+use anchor_spl::token::spl_token::instruction::AuthorityType;
 use anchor_spl::associated_token::AssociatedToken;
 
+pub mod allowlist;
+pub mod batch;
+pub mod curve;
+pub mod holders;
+pub mod oracle;
+pub mod symbol;
+pub mod validation;
+pub mod vesting;
+
+use curve::{BuyQuote, CurvePosition, CurveType, FeeSchedule, TradeFees, BASIS_POINTS};
+use holders::{TopHolder, TOP_HOLDERS};
+use oracle::{Observation, OBSERVATIONS};
+
 declare_id!("CRJDPpTp3aayKYZCaLEYntnpP3xvwbeTDYMdu18RtHwh");
+
+// Constants for safer math
+const DECIMALS: u8 = 6;
+const TOKEN_MULTIPLIER: u64 = 1_000_000; // 10^6 for 6 decimals
+
+// Anti-Bundler Protection: Progressive wallet limits
+const MAX_WALLET_LAUNCH_BPS: u16 = 50;  // 0.5% max during launch (first 15 min)
+const MAX_WALLET_NORMAL_BPS: u16 = 200;  // 2% max after launch period
+const LAUNCH_PERIOD_SECONDS: i64 = 900;  // 15 minutes
+
+// Anti-Bot Protection: Cooldowns
+const TRADE_COOLDOWN_SECONDS: i64 = 1;   // 1 second between trades
+const LAUNCH_COOLDOWN_SECONDS: i64 = 60; // 60 second cooldown after token creation
+const LAUNCH_COOLDOWN_SLOTS: u64 = 150;  // The same cooldown at ~400ms slots
+
+// Creator Allocation: Bounded and vested so creators can't dump at launch
+const MAX_CREATOR_ALLOCATION_BPS: u16 = 500;         // 5% of total supply
+const MIN_VESTING_CLIFF_SECONDS: i64 = 7 * 86_400;    // 7 days
+const MIN_VESTING_DURATION_SECONDS: i64 = 30 * 86_400; // 30 days
+
+// Anti-Rug: Starting prices come from protocol presets, or custom reserves
+// within protocol bounds, so creators can't launch at a trap price
+const MAX_CURVE_PRESETS: usize = 4;
+const DEFAULT_CURVE_PRESET: CurvePreset = CurvePreset {
+    virtual_sol_reserves: 30_000_000_000,                      // 30 SOL
+    virtual_token_reserves: 1_073_000_000 * TOKEN_MULTIPLIER, // 1.073B tokens
+};
+const DEFAULT_RESERVE_BOUNDS: ReserveBounds = ReserveBounds {
+    min_virtual_sol: 10_000_000_000,    // 10 SOL
+    max_virtual_sol: 500_000_000_000,   // 500 SOL
+    min_virtual_token: 900_000_000 * TOKEN_MULTIPLIER,
+    max_virtual_token: 2_000_000_000 * TOKEN_MULTIPLIER,
+};
+
+// Anti-Snipe: Launch fee schedule bounds
+const MAX_PROTOCOL_FEE_BPS: u16 = 1_000; // 10% steady-state fee
+const MAX_LAUNCH_FEE_BPS: u16 = 2_500;   // 25% right at launch
+
+// Anti-Sniper: Early-flip sell tax bounds for LaunchPolicy
+const MAX_FLIP_TAX_BPS: u16 = 2_000;                // 20%
+const MAX_FLIP_TAX_WINDOW_SECONDS: i64 = 86_400;    // 24 hours
+
+// Anti-Bot: Allowlist phase bounds for LaunchPolicy
+const MAX_ALLOWLIST_WINDOW_SECONDS: i64 = 86_400;   // 24 hours
+
+// Anti-Bot: Commit-reveal launch window bounds for LaunchPolicy
+const MAX_COMMIT_WINDOW_SLOTS: u64 = 9_000;         // ~1 hour
+const MAX_REVEAL_WINDOW_SLOTS: u64 = 9_000;
+
+// Anti-Bot: Batch auction bounds for LaunchPolicy
+const MAX_AUCTION_WINDOW_SLOTS: u64 = 9_000;        // ~1 hour
+const MAX_AUCTION_TRANCHE_BPS: u16 = 5_000;         // Half the curve supply
+
+// Anti-Bundler: Wallets a creator can deny during the launch period
+const MAX_DENYLIST_WALLETS: usize = 200;
+
+// Anti-Bundler: Launch-period buyer balance gate bound for LaunchPolicy
+const MAX_MIN_BUYER_BALANCE_LAMPORTS: u64 = 100_000_000_000; // 100 SOL
+
+// Referrals: share of the protocol fee paid to the trade's referrer
+const MAX_REFERRAL_SHARE_BPS: u16 = 5_000; // At most half the protocol fee
+
+// Anti-Squatting: Symbol reservations lapse if the token never trades
+const SYMBOL_RESERVATION_TTL_SECONDS: i64 = 86_400; // 24 hours
+
+#[program]
+pub mod meme_chain {
+    use super::*;
+
+    pub fn initialize_protocol(
+        ctx: Context<InitializeProtocol>,
+        protocol_fee_bps: u16,
+        creation_fee_lamports: u64,
+        graduation_threshold: u64,
+        max_initial_buy_lamports: u64,
+    ) -> Result<()> {
+        require!(protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS, ErrorCode::FeeTooHigh);
+        
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.authority = ctx.accounts.authority.key();
+        protocol.treasury_split = TreasurySplit {
+            team_bps: BASIS_POINTS as u16,
+            buyback_bps: 0,
+            insurance_bps: 0,
+        };
+        protocol.protocol_fee_bps = protocol_fee_bps;
+        // Flat until configure_fee_schedule sets a launch fee
+        protocol.fee_schedule = FeeSchedule {
+            start_bps: protocol_fee_bps,
+            end_bps: protocol_fee_bps,
+            decay_slots: 0,
+        };
+        protocol.creation_fee_lamports = creation_fee_lamports;
+        protocol.graduation_threshold = graduation_threshold;
+        protocol.max_initial_buy_lamports = max_initial_buy_lamports;
+        protocol.curve_presets = [CurvePreset::default(); MAX_CURVE_PRESETS];
+        protocol.curve_presets[0] = DEFAULT_CURVE_PRESET;
+        protocol.curve_preset_count = 1;
+        protocol.reserve_bounds = DEFAULT_RESERVE_BOUNDS;
+        protocol.referral_share_bps = 0;
+        protocol.attester = Pubkey::default();
+        protocol.total_memes_created = 0;
+        protocol.total_volume = 0;
+        protocol.total_buy_volume = 0;
+        protocol.total_sell_volume = 0;
+        protocol.total_protocol_fees = 0;
+        protocol.total_creator_fees = 0;
+        protocol.total_trades = 0;
+        protocol.total_graduations = 0;
+        protocol.bump = ctx.bumps.protocol;
+        
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.team_balance = 0;
+        treasury.buyback_balance = 0;
+        treasury.insurance_balance = 0;
+        treasury.total_collected = 0;
+        treasury.total_withdrawn = 0;
+        treasury.bump = ctx.bumps.treasury;
+        
+        msg!("Protocol initialized with {}% fee", protocol_fee_bps as f64 / 100.0);
+        Ok(())
+    }
+
+    // Anti-Snipe: trades right after launch pay start_bps, decaying linearly
+    // to end_bps (the steady-state protocol fee) over the decay_slots after
+    // the launch cooldown
+    pub fn configure_fee_schedule(ctx: Context<UpdateProtocol>, schedule: FeeSchedule) -> Result<()> {
+        require!(schedule.end_bps <= MAX_PROTOCOL_FEE_BPS, ErrorCode::FeeTooHigh);
+        require!(schedule.start_bps <= MAX_LAUNCH_FEE_BPS, ErrorCode::FeeTooHigh);
+        require!(schedule.start_bps >= schedule.end_bps, ErrorCode::InvalidFeeSchedule);
+        
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.fee_schedule = schedule;
+        protocol.protocol_fee_bps = schedule.end_bps;
+        
+        msg!(
+            "Fee schedule: {} -> {} bps over {} slots",
+            schedule.start_bps,
+            schedule.end_bps,
+            schedule.decay_slots
+        );
+        Ok(())
+    }
+
+    // Share of the protocol fee paid to referrers, in bps of that fee
+    pub fn configure_referral_share(ctx: Context<UpdateProtocol>, referral_share_bps: u16) -> Result<()> {
+        require!(referral_share_bps <= MAX_REFERRAL_SHARE_BPS, ErrorCode::FeeTooHigh);
+        ctx.accounts.protocol.referral_share_bps = referral_share_bps;
+        
+        msg!("Referral share set to {} bps of the protocol fee", referral_share_bps);
+        Ok(())
+    }
+
+    // Anti-Bundler: key that issues Attestations for launch-period buys.
+    // Rotating it invalidates everything the previous key issued.
+    pub fn configure_attester(ctx: Context<UpdateProtocol>, attester: Pubkey) -> Result<()> {
+        ctx.accounts.protocol.attester = attester;
+        
+        msg!("Attester set to {}", attester);
+        Ok(())
+    }
+
+    // How newly collected protocol fees are divided between treasury buckets
+    pub fn configure_treasury_split(ctx: Context<UpdateProtocol>, split: TreasurySplit) -> Result<()> {
+        let total = split.team_bps as u64 + split.buyback_bps as u64 + split.insurance_bps as u64;
+        require!(total == BASIS_POINTS, ErrorCode::InvalidTreasurySplit);
+        ctx.accounts.protocol.treasury_split = split;
+        
+        msg!(
+            "Treasury split: team {} / buyback {} / insurance {} bps",
+            split.team_bps,
+            split.buyback_bps,
+            split.insurance_bps
+        );
+        Ok(())
+    }
+
+    // Move protocol fees out of one treasury bucket
+    pub fn withdraw_protocol_fees(
+        ctx: Context<WithdrawProtocolFees>,
+        amount: u64,
+        bucket: TreasuryBucket,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        let treasury = &mut ctx.accounts.treasury;
+        let balance = match bucket {
+            TreasuryBucket::Team => &mut treasury.team_balance,
+            TreasuryBucket::Buyback => &mut treasury.buyback_balance,
+            TreasuryBucket::Insurance => &mut treasury.insurance_balance,
+        };
+        *balance = balance
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+        treasury.total_withdrawn = treasury.total_withdrawn
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        // Fees sit on top of the treasury's rent-exempt minimum
+        let info = treasury.to_account_info();
+        let rent_minimum = Rent::get()?.minimum_balance(8 + ProtocolTreasury::INIT_SPACE);
+        let remaining = curve::vault_balance_after_withdrawal(info.lamports(), amount, rent_minimum)?;
+        **info.try_borrow_mut_lamports()? = remaining;
+        credit_lamports(&ctx.accounts.recipient, amount)?;
+        
+        emit!(TreasuryWithdrawal {
+            bucket,
+            amount,
+            recipient: ctx.accounts.recipient.key(),
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Treasury withdrawal: {} lamports to {}", amount, ctx.accounts.recipient.key());
+        Ok(())
+    }
+
+    // Replace the starting-price presets and the bounds for custom reserves
+    pub fn configure_curve_presets(
+        ctx: Context<UpdateProtocol>,
+        presets: Vec<CurvePreset>,
+        bounds: ReserveBounds,
+    ) -> Result<()> {
+        require!(
+            !presets.is_empty() && presets.len() <= MAX_CURVE_PRESETS,
+            ErrorCode::InvalidCurvePreset
+        );
+        require!(
+            bounds.min_virtual_sol > 0
+                && bounds.min_virtual_token > 0
+                && bounds.min_virtual_sol <= bounds.max_virtual_sol
+                && bounds.min_virtual_token <= bounds.max_virtual_token,
+            ErrorCode::InvalidReserves
+        );
+        for preset in &presets {
+            require!(
+                bounds.contains(preset.virtual_sol_reserves, preset.virtual_token_reserves),
+                ErrorCode::ReservesOutOfBounds
+            );
+        }
+        
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.curve_presets = [CurvePreset::default(); MAX_CURVE_PRESETS];
+        protocol.curve_presets[..presets.len()].copy_from_slice(&presets);
+        protocol.curve_preset_count = presets.len() as u8;
+        protocol.reserve_bounds = bounds;
+        
+        msg!("Curve presets updated: {} tiers", presets.len());
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_meme_token(
+        ctx: Context<CreateMemeToken>,
+        name: String,
+        symbol: String,
+        uri: String,
+        image_hash: [u8; 32],
+        perceptual_hash: u64,
+        initial_reserves: InitialReserves,
+        curve_type: CurveType,
+        creator_vesting: Option<CreatorVestingParams>,
+        initial_buy_sol: Option<u64>,
+        launch_policy: LaunchPolicy,
+    ) -> Result<()> {
+        require!(name.len() <= 32, ErrorCode::NameTooLong);
+        require!(!name.is_empty(), ErrorCode::NameTooShort);
+        require!(symbol.len() <= 10, ErrorCode::SymbolTooLong);
+        require!(!symbol.is_empty(), ErrorCode::SymbolTooShort);
+        require!(uri.len() <= 200, ErrorCode::UriTooLong);
+        validation::validate_name(&name)?;
+        validation::validate_symbol(&symbol)?;
+        validation::validate_uri(&uri)?;
+        curve::validate_curve_type(&curve_type)?;
+        require!(
+            launch_policy.flip_tax_bps <= MAX_FLIP_TAX_BPS
+                && (0..=MAX_FLIP_TAX_WINDOW_SECONDS).contains(&launch_policy.flip_tax_window_seconds),
+            ErrorCode::InvalidLaunchPolicy
+        );
+        let allowlist_window = if launch_policy.allowlist_root == [0; 32] {
+            0..=0
+        } else {
+            // Has to outlast the launch cooldown, or no allowlisted buy could land
+            LAUNCH_COOLDOWN_SECONDS + 1..=MAX_ALLOWLIST_WINDOW_SECONDS
+        };
+        require!(
+            allowlist_window.contains(&launch_policy.allowlist_window_seconds),
+            ErrorCode::InvalidLaunchPolicy
+        );
+        let commit_reveal = launch_policy.commit_window_slots > 0;
+        let reveal_window = if commit_reveal { 1..=MAX_REVEAL_WINDOW_SLOTS } else { 0..=0 };
+        require!(
+            launch_policy.commit_window_slots <= MAX_COMMIT_WINDOW_SLOTS
+                && reveal_window.contains(&launch_policy.reveal_window_slots),
+            ErrorCode::InvalidLaunchPolicy
+        );
+        // Auctions reset the virtual reserves to the clearing price, which
+        // only a constant product curve can carry on from
+        let auction = launch_policy.auction_window_slots > 0;
+        let (tranche_bounds, bid_cap_bounds) = if auction {
+            (1..=MAX_AUCTION_TRANCHE_BPS, 1..=ctx.accounts.protocol.max_initial_buy_lamports)
+        } else {
+            (0..=0, 0..=0)
+        };
+        require!(
+            launch_policy.auction_window_slots <= MAX_AUCTION_WINDOW_SLOTS
+                && tranche_bounds.contains(&launch_policy.auction_tranche_bps)
+                && bid_cap_bounds.contains(&launch_policy.auction_max_bid_lamports)
+                && !(auction && (commit_reveal || curve_type != CurveType::ConstantProduct)),
+            ErrorCode::InvalidLaunchPolicy
+        );
+        // The creator joins the batch like everyone else instead of dev-buying ahead of it
+        let batch_launch = commit_reveal || auction;
+        require!(
+            !(batch_launch && initial_buy_sol.is_some())
+                && batch_launch == ctx.accounts.launch_batch.is_some(),
+            ErrorCode::InvalidLaunchPolicy
+        );
+        require!(
+            launch_policy.denylist_enabled == ctx.accounts.denylist.is_some(),
+            ErrorCode::InvalidLaunchPolicy
+        );
+        require!(
+            launch_policy.min_buyer_balance_lamports <= MAX_MIN_BUYER_BALANCE_LAMPORTS
+                && !(launch_policy.require_attestation && ctx.accounts.protocol.attester == Pubkey::default()),
+            ErrorCode::InvalidLaunchPolicy
+        );
+        // Virtual reserves only price the constant product curve
+        let (initial_virtual_sol_reserves, initial_virtual_token_reserves) =
+            if curve_type == CurveType::ConstantProduct {
+                ctx.accounts.protocol.resolve_reserves(initial_reserves)?
+            } else {
+                (0, 0)
+            };
+
+        // Anti-PVP: Check image hash is not all zeros (must be unique)
+        let is_zero_hash = image_hash.iter().all(|&b| b == 0);
+        require!(!is_zero_hash, ErrorCode::InvalidImageHash);
+        
+        let protocol = &mut ctx.accounts.protocol;
+        let meme = &mut ctx.accounts.meme;
+        let clock = Clock::get()?;
+        
+        // Anti-PVP: First launch of an image claims its registry entry,
+        // copycat launches of the same art are rejected
+        let image_registry = &mut ctx.accounts.image_registry;
+        require!(image_registry.meme == Pubkey::default(), ErrorCode::DuplicateMeme);
+        image_registry.image_hash = image_hash;
+        image_registry.meme = meme.key();
+        image_registry.creator = ctx.accounts.creator.key();
+        image_registry.created_at = clock.unix_timestamp;
+        image_registry.bump = ctx.bumps.image_registry;
+
+        // Total supply: 1 billion tokens (with 6 decimals)
+        let total_supply = 1_000_000_000 * TOKEN_MULTIPLIER;
+        let (curve_supply, migration_reserve) = curve::split_supply(total_supply)?;
+        
+        // Creator allocation is carved out of the curve allocation and locked
+        let creator_allocation = match creator_vesting {
+            Some(params) => {
+                require!(
+                    params.allocation_bps > 0 && params.allocation_bps <= MAX_CREATOR_ALLOCATION_BPS,
+                    ErrorCode::CreatorAllocationTooHigh
+                );
+                require!(
+                    params.cliff_seconds >= MIN_VESTING_CLIFF_SECONDS
+                        && params.duration_seconds >= MIN_VESTING_DURATION_SECONDS
+                        && params.cliff_seconds <= params.duration_seconds,
+                    ErrorCode::InvalidVestingSchedule
+                );
+                curve::fee_amount(total_supply, params.allocation_bps)?
+            }
+            None => {
+                require!(
+                    ctx.accounts.creator_vesting.is_none() && ctx.accounts.vesting_vault.is_none(),
+                    ErrorCode::InvalidVestingSchedule
+                );
+                0
+            }
+        };
+        let bonding_curve_supply = curve_supply
+            .checked_sub(creator_allocation)
+            .ok_or(ErrorCode::Overflow)?;
+        // Anti-Rug: shaped curves start at a price custom reserves could
+        // have given, and can't cost more than a u64 of lamports to buy out
+        curve::validate_curve_bounds(
+            &curve_type,
+            bonding_curve_supply,
+            protocol.reserve_bounds.price_range(),
+        )?;
+
+        meme.creator = ctx.accounts.creator.key();
+        meme.mint = ctx.accounts.mint.key();
+        meme.name = name.clone();
+        meme.symbol = symbol.clone();
+        meme.uri = uri.clone();
+        meme.image_hash = image_hash;
+        meme.perceptual_hash = perceptual_hash;
+        meme.created_at = clock.unix_timestamp;
+        meme.created_slot = clock.slot;
+        meme.total_supply = total_supply;
+        meme.circulating_supply = 0;
+        meme.bonding_curve_supply = bonding_curve_supply;
+        meme.migration_reserve = migration_reserve;
+        meme.is_graduated = false;
+        meme.amm_migrated = false;
+        meme.amm_type = None;
+        meme.total_volume = 0;
+        meme.holders_count = 0;
+        meme.creator_allocation = creator_allocation;
+        meme.dev_buy_sol = 0;
+        meme.dev_buy_tokens = 0;
+        meme.creator_fee_bps = 0;
+        meme.creator_fees_earned = 0;
+        meme.last_trade_timestamp = clock.unix_timestamp;
+        meme.trade_count = 0;
+        meme.id = protocol.total_memes_created;
+        meme.bump = ctx.bumps.meme;
+        
+        // Bonding curve parameters (Pump.fun style)
+        meme.virtual_sol_reserves = initial_virtual_sol_reserves;
+        meme.virtual_token_reserves = initial_virtual_token_reserves;
+        meme.curve_type = curve_type;
+        meme.launch_policy = launch_policy;
+        meme.launch_batch_pending = batch_launch;
+        meme.real_sol_reserves = 0;
+        meme.real_token_reserves = total_supply - creator_allocation; // Everything else starts in the token vault
+        
+        let vault = &mut ctx.accounts.bonding_curve_vault;
+        vault.meme = meme.key();
+        vault.bump = ctx.bumps.bonding_curve_vault;
+        
+        let holder_stats = &mut ctx.accounts.holder_stats;
+        holder_stats.meme = meme.key();
+        holder_stats.bump = ctx.bumps.holder_stats;
+        
+        let reward_pool = &mut ctx.accounts.reward_pool;
+        reward_pool.meme = meme.key();
+        reward_pool.total_collected = 0;
+        reward_pool.bump = ctx.bumps.reward_pool;
+        
+        let price_oracle = &mut ctx.accounts.price_oracle;
+        price_oracle.meme = meme.key();
+        price_oracle.last_timestamp = clock.unix_timestamp;
+        price_oracle.bump = ctx.bumps.price_oracle;
+        
+        if let Some(launch_batch) = ctx.accounts.launch_batch.as_mut() {
+            launch_batch.meme = meme.key();
+            launch_batch.bump = ctx.bumps.launch_batch.ok_or(ErrorCode::InvalidLaunchPolicy)?;
+        }
+        
+        if let Some(denylist) = ctx.accounts.denylist.as_mut() {
+            denylist.meme = meme.key();
+            denylist.bump = ctx.bumps.denylist.ok_or(ErrorCode::InvalidLaunchPolicy)?;
+        }
+        
+        // Fixed supply: mint everything into the token vault once, then revoke
+        // the mint authority so no further tokens can ever be created
+        let meme_key = meme.key();
+        let seeds = &[
+            b"mint",
+            meme_key.as_ref(),
+            &[ctx.bumps.mint],
+        ];
+        let signer = &[&seeds[..]];
+        
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.token_vault.to_account_info(),
+                    authority: ctx.accounts.mint.to_account_info(),
+                },
+                signer,
+            ),
+            total_supply - creator_allocation,
+        )?;
+        
+        if let Some(params) = creator_vesting {
+            let (Some(vesting), Some(vesting_vault)) = (
+                ctx.accounts.creator_vesting.as_mut(),
+                ctx.accounts.vesting_vault.as_ref(),
+            ) else {
+                return err!(ErrorCode::InvalidVestingSchedule);
+            };
+            
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: vesting_vault.to_account_info(),
+                        authority: ctx.accounts.mint.to_account_info(),
+                    },
+                    signer,
+                ),
+                creator_allocation,
+            )?;
+            
+            vesting.meme = meme_key;
+            vesting.creator = ctx.accounts.creator.key();
+            vesting.total_amount = creator_allocation;
+            vesting.claimed_amount = 0;
+            vesting.start = clock.unix_timestamp;
+            vesting.cliff_seconds = params.cliff_seconds;
+            vesting.duration_seconds = params.duration_seconds;
+            vesting.bump = ctx.bumps.creator_vesting.ok_or(ErrorCode::InvalidVestingSchedule)?;
+            
+            msg!("Creator allocation {} tokens vesting over {}s", creator_allocation, params.duration_seconds);
+        }
+        
+        // Creator dev-buy: an atomic first buy on the curve, so the creator
+        // doesn't have to race bots once the launch cooldown ends
+        if let Some(initial_buy_sol) = initial_buy_sol {
+            require!(initial_buy_sol > 0, ErrorCode::InvalidAmount);
+            require!(
+                initial_buy_sol <= protocol.max_initial_buy_lamports,
+                ErrorCode::DevBuyTooLarge
+            );
+            let Some(wallet_state) = ctx.accounts.creator_wallet_state.as_mut() else {
+                return err!(ErrorCode::InvalidAmount);
+            };
+            
+            let quote = curve::quote_buy_on(
+                &meme.curve_position(),
+                initial_buy_sol,
+                meme.bonding_curve_supply,
+            )?;
+            let sol_amount = quote.sol_in;
+            let tokens_out = quote.tokens_out;
+            require!(tokens_out > 0, ErrorCode::InvalidAmount);
+            
+            // Anti-Bundler: the dev-buy is held to the launch-period wallet cap
+            let creator_new_balance = ctx.accounts.creator_token_account.amount
+                .checked_add(tokens_out)
+                .ok_or(ErrorCode::Overflow)?;
+            let max_wallet_amount = curve::fee_amount(meme.total_supply, MAX_WALLET_LAUNCH_BPS)?;
+            require!(
+                creator_new_balance <= max_wallet_amount,
+                ErrorCode::MaxWalletExceeded
+            );
+            
+            meme.virtual_sol_reserves = quote.new_virtual_sol;
+            meme.virtual_token_reserves = quote.new_virtual_token;
+            meme.real_sol_reserves = sol_amount;
+            meme.real_token_reserves = meme.real_token_reserves
+                .checked_sub(tokens_out)
+                .ok_or(ErrorCode::InsufficientFunds)?;
+            meme.circulating_supply = tokens_out;
+            meme.total_volume = sol_amount;
+            meme.trade_count = 1;
+            meme.dev_buy_sol = sol_amount;
+            meme.dev_buy_tokens = tokens_out;
+            
+            // Charged on top of the curve input like any other buy, at the
+            // steady-state fee: the launch fee is aimed at outside snipers
+            let fees = curve::trade_fees(sol_amount, protocol.protocol_fee_bps, meme.creator_fee_bps)?;
+            // No creator fee changes hands: the creator would be paying themselves
+            protocol.record_trade(true, sol_amount, fees.protocol_fee, 0)?;
+            let ix = anchor_lang::solana_program::system_instruction::transfer(
+                &ctx.accounts.creator.key(),
+                &ctx.accounts.bonding_curve_vault.key(),
+                sol_amount,
+            );
+            anchor_lang::solana_program::program::invoke(
+                &ix,
+                &[
+                    ctx.accounts.creator.to_account_info(),
+                    ctx.accounts.bonding_curve_vault.to_account_info(),
+                ],
+            )?;
+            if fees.protocol_fee > 0 {
+                let ix = anchor_lang::solana_program::system_instruction::transfer(
+                    &ctx.accounts.creator.key(),
+                    &ctx.accounts.treasury.key(),
+                    fees.protocol_fee,
+                );
+                anchor_lang::solana_program::program::invoke(
+                    &ix,
+                    &[
+                        ctx.accounts.creator.to_account_info(),
+                        ctx.accounts.treasury.to_account_info(),
+                    ],
+                )?;
+                ctx.accounts.treasury.accrue(fees.protocol_fee, &protocol.treasury_split)?;
+            }
+            
+            let vault_seeds = &[
+                b"token_vault",
+                meme_key.as_ref(),
+                &[ctx.bumps.token_vault],
+            ];
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.token_vault.to_account_info(),
+                        to: ctx.accounts.creator_token_account.to_account_info(),
+                        authority: ctx.accounts.token_vault.to_account_info(),
+                    },
+                    &[&vault_seeds[..]],
+                ),
+                tokens_out,
+            )?;
+            
+            wallet_state.meme = meme_key;
+            wallet_state.wallet = ctx.accounts.creator.key();
+            wallet_state.first_buy_at = clock.unix_timestamp;
+            wallet_state.bump = ctx.bumps.creator_wallet_state.ok_or(ErrorCode::InvalidAmount)?;
+            record_holder_balance(meme, wallet_state, &mut ctx.accounts.holder_stats, creator_new_balance)?;
+            
+            msg!("Dev-buy: {} SOL -> {} tokens", sol_amount, tokens_out);
+        }
+        
+        // First oracle snapshot at the post-launch price, after any dev-buy
+        let spot_price = curve::spot_price(&meme.curve_position())?;
+        ctx.accounts.price_oracle.record_price(spot_price, clock.unix_timestamp);
+        
+        token::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.mint.to_account_info(),
+                    account_or_mint: ctx.accounts.mint.to_account_info(),
+                },
+                signer,
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        protocol.total_memes_created += 1;
+        
+        emit!(MemeCreated {
+            meme: meme_key,
+            creator: meme.creator,
+            mint: meme.mint,
+            name: meme.name.clone(),
+            symbol: meme.symbol.clone(),
+            creator_allocation,
+            dev_buy_sol: meme.dev_buy_sol,
+            dev_buy_tokens: meme.dev_buy_tokens,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Meme token '{}' created with supply {} tokens", meme.name, total_supply);
+        Ok(())
+    }
+
+    pub fn buy_tokens(
+        ctx: Context<BuyTokens>,
+        sol_amount: u64,
+        min_tokens_out: u64,
+        max_slippage_bps: u16,
+        allowlist: Option<AllowlistEntry>,
+    ) -> Result<()> {
+        let meme = &mut ctx.accounts.meme;
+        let protocol = &mut ctx.accounts.protocol;
+        let clock = Clock::get()?;
+        
+        require!(sol_amount > 0, ErrorCode::InvalidAmount);
+        require!(max_slippage_bps <= 5000, ErrorCode::SlippageTooHigh); // Max 50%
+        
+        // Graduation, trade cooldown and launch cooldown
+        if let Some(rejection) = trade_rejection(meme, clock.unix_timestamp, true) {
+            return Err(rejection.into());
+        }
+        
+        // Bonding curve supply cap: never sell past the curve allocation
+        let remaining_supply = curve::remaining_curve_supply(
+            meme.bonding_curve_supply,
+            meme.circulating_supply,
+        );
+        require!(remaining_supply > 0, ErrorCode::CurveSoldOut);
+        
+        // Price the buy on the meme's curve, clamped to the remaining
+        // allocation. A clamped buy only spends the SOL it needs.
+        let quote = curve::quote_buy_on(
+            &meme.curve_position(),
+            sol_amount,
+            remaining_supply,
+        )?;
+        let sol_amount = quote.sol_in;
+        let tokens_out = quote.tokens_out;
+        
+        require!(tokens_out >= min_tokens_out, ErrorCode::SlippageExceeded);
+        require!(tokens_out > 0, ErrorCode::InvalidAmount);
+        
+        // Anti-Bot: allowlisted wallets only until the allowlist window ends
+        if let Some(rejection) = allowlist_rejection(
+            meme,
+            &ctx.accounts.buyer.key(),
+            ctx.accounts.wallet_state.allowlist_spent,
+            sol_amount,
+            allowlist.as_ref(),
+            clock.unix_timestamp,
+        ) {
+            return Err(rejection.into());
+        }
+        
+        // Anti-Bundler: wallets the creator denied can't buy during launch
+        if let Some(rejection) = denylist_rejection(
+            meme,
+            &ctx.accounts.buyer.key(),
+            ctx.accounts.denylist.as_deref().map(|denylist| &**denylist),
+            clock.unix_timestamp,
+        ) {
+            return Err(rejection.into());
+        }
+        
+        // Anti-Bundler: Check wallet limit
+        let buyer_new_balance = ctx.accounts.buyer_token_account.amount
+            .checked_add(tokens_out)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        require!(
+            buyer_new_balance <= max_wallet_amount(meme, clock.unix_timestamp)?,
+            ErrorCode::MaxWalletExceeded
+        );
+        
+        // Update reserves
+        meme.virtual_sol_reserves = quote.new_virtual_sol;
+        meme.virtual_token_reserves = quote.new_virtual_token;
+        meme.real_sol_reserves = meme.real_sol_reserves
+            .checked_add(sol_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        meme.real_token_reserves = meme.real_token_reserves
+            .checked_sub(tokens_out)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+        meme.circulating_supply = meme.circulating_supply
+            .checked_add(tokens_out)
+            .ok_or(ErrorCode::Overflow)?;
+        let reserved = meme.migration_reserve
+            .checked_add(meme.creator_allocation)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(
+            curve::supply_within_cap(meme.total_supply, meme.circulating_supply, reserved),
+            ErrorCode::CurveSupplyExceeded
+        );
+        meme.total_volume = meme.total_volume
+            .checked_add(sol_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        meme.last_trade_timestamp = clock.unix_timestamp;
+        meme.trade_count = meme.trade_count
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        // Calculate fees. Fees are charged on top of the curve input so the
+        // vault receives exactly what was credited to real_sol_reserves.
+        // Part of the protocol fee goes to whoever referred the trade.
+        if let Some(referrer_state) = ctx.accounts.referrer_state.as_ref() {
+            require!(referrer_state.referrer != ctx.accounts.buyer.key(), ErrorCode::SelfReferral);
+        }
+        let (protocol_fee_bps, fees) = current_trade_fees(
+            protocol,
+            meme,
+            clock.slot,
+            sol_amount,
+            ctx.accounts.referrer_state.is_some(),
+        )?;
+        let protocol_fee = fees.protocol_fee;
+        let creator_fee = fees.creator_fee;
+        let referral_fee = fees.referral_fee;
+        protocol.record_trade(true, sol_amount, protocol_fee, creator_fee)?;
+        
+        // Transfer SOL from buyer to vault
+        let ix = anchor_lang::solana_program::system_instruction::transfer(
+            &ctx.accounts.buyer.key(),
+            &ctx.accounts.bonding_curve_vault.key(),
+            sol_amount,
+        );
+        anchor_lang::solana_program::program::invoke(
+            &ix,
+            &[
+                ctx.accounts.buyer.to_account_info(),
+                ctx.accounts.bonding_curve_vault.to_account_info(),
+            ],
+        )?;
+        
+        // Transfer protocol fee into the treasury
+        if protocol_fee > 0 {
+            let ix = anchor_lang::solana_program::system_instruction::transfer(
+                &ctx.accounts.buyer.key(),
+                &ctx.accounts.treasury.key(),
+                protocol_fee,
+            );
+            anchor_lang::solana_program::program::invoke(
+                &ix,
+                &[
+                    ctx.accounts.buyer.to_account_info(),
+                    ctx.accounts.treasury.to_account_info(),
+                ],
+            )?;
+            ctx.accounts.treasury.accrue(protocol_fee, &protocol.treasury_split)?;
+        }
+        
+        // Transfer creator fee
+        if creator_fee > 0 {
+            let ix = anchor_lang::solana_program::system_instruction::transfer(
+                &ctx.accounts.buyer.key(),
+                &ctx.accounts.creator.key(),
+                creator_fee,
+            );
+            anchor_lang::solana_program::program::invoke(
+                &ix,
+                &[
+                    ctx.accounts.buyer.to_account_info(),
+                    ctx.accounts.creator.to_account_info(),
+                ],
+            )?;
+            meme.creator_fees_earned = meme.creator_fees_earned
+                .checked_add(creator_fee)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+        
+        // Transfer referral fee
+        if let Some(referrer_state) = ctx.accounts.referrer_state.as_mut() {
+            if referral_fee > 0 {
+                let ix = anchor_lang::solana_program::system_instruction::transfer(
+                    &ctx.accounts.buyer.key(),
+                    &referrer_state.key(),
+                    referral_fee,
+                );
+                anchor_lang::solana_program::program::invoke(
+                    &ix,
+                    &[
+                        ctx.accounts.buyer.to_account_info(),
+                        referrer_state.to_account_info(),
+                    ],
+                )?;
+                referrer_state.accrue(referral_fee)?;
+            }
+        }
+        
+        // Anti-Bundler: launch gate, checked once the buyer has paid everything
+        if let Some(rejection) = launch_gate_rejection(
+            meme,
+            protocol,
+            ctx.accounts.attestation.as_deref(),
+            ctx.accounts.buyer.lamports(),
+            clock.unix_timestamp,
+        ) {
+            return Err(rejection.into());
+        }
+        
+        // Transfer tokens from the vault to buyer
+        let meme_key = meme.key();
+        let seeds = &[
+            b"token_vault",
+            meme_key.as_ref(),
+            &[ctx.bumps.token_vault],
+        ];
+        let signer = &[&seeds[..]];
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.token_vault.to_account_info(),
+                    to: ctx.accounts.buyer_token_account.to_account_info(),
+                    authority: ctx.accounts.token_vault.to_account_info(),
+                },
+                signer,
+            ),
+            tokens_out,
+        )?;
+        
+        // Holder tracking: buyer_new_balance is the post-trade balance
+        let wallet_state = &mut ctx.accounts.wallet_state;
+        if wallet_state.wallet == Pubkey::default() {
+            wallet_state.meme = meme_key;
+            wallet_state.wallet = ctx.accounts.buyer.key();
+            wallet_state.bump = ctx.bumps.wallet_state;
+        }
+        if wallet_state.first_buy_at == 0 {
+            wallet_state.first_buy_at = clock.unix_timestamp;
+        }
+        if meme.allowlist_active(clock.unix_timestamp) {
+            wallet_state.allowlist_spent = wallet_state.allowlist_spent
+                .checked_add(sol_amount)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+        record_holder_balance(meme, wallet_state, &mut ctx.accounts.holder_stats, buyer_new_balance)?;
+        
+        let spot_price = curve::spot_price(&meme.curve_position())?;
+        ctx.accounts.price_oracle.record_price(spot_price, clock.unix_timestamp);
+        
+        // Curve allocation exhausted: remaining liquidity moves to the AMM
+        if meme.circulating_supply == meme.bonding_curve_supply {
+            meme.is_graduated = true;
+            protocol.total_graduations = protocol.total_graduations
+                .checked_add(1)
+                .ok_or(ErrorCode::Overflow)?;
+            msg!("Bonding curve sold out - token graduated");
+        }
+        
+        emit!(TradeEvent {
+            meme: meme_key,
+            trader: ctx.accounts.buyer.key(),
+            is_buy: true,
+            sol_amount,
+            token_amount: tokens_out,
+            protocol_fee,
+            protocol_fee_bps,
+            creator_fee,
+            referral_fee,
+            flip_tax: 0,
+            virtual_sol_reserves: meme.virtual_sol_reserves,
+            virtual_token_reserves: meme.virtual_token_reserves,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Buy: {} SOL -> {} tokens", sol_amount, tokens_out);
+        Ok(())
+    }
+
+    pub fn sell_tokens(
+        ctx: Context<SellTokens>,
+        token_amount: u64,
+        min_sol_out: u64,
+        max_slippage_bps: u16,
+    ) -> Result<()> {
+        let meme = &mut ctx.accounts.meme;
+        let protocol = &mut ctx.accounts.protocol;
+        let clock = Clock::get()?;
+        
+        require!(token_amount > 0, ErrorCode::InvalidAmount);
+        require!(max_slippage_bps <= 5000, ErrorCode::SlippageTooHigh);
+        
+        // Graduation and trade cooldown
+        if let Some(rejection) = trade_rejection(meme, clock.unix_timestamp, false) {
+            return Err(rejection.into());
+        }
+        
+        // Price the sell on the meme's curve
+        let quote = curve::quote_sell_on(
+            &meme.curve_position(),
+            token_amount,
+        )?;
+        let sol_out = quote.sol_out;
+        
+        require!(sol_out >= min_sol_out, ErrorCode::SlippageExceeded);
+        require!(sol_out > 0, ErrorCode::InvalidAmount);
+        
+        // Calculate fees. They are paid out of sol_out, which leaves the vault
+        // in full, so the vault and real_sol_reserves move by the same amount.
+        // Part of the protocol fee goes to whoever referred the trade.
+        if let Some(referrer_state) = ctx.accounts.referrer_state.as_ref() {
+            require!(referrer_state.referrer != ctx.accounts.seller.key(), ErrorCode::SelfReferral);
+        }
+        let (protocol_fee_bps, fees) = current_trade_fees(
+            protocol,
+            meme,
+            clock.slot,
+            sol_out,
+            ctx.accounts.referrer_state.is_some(),
+        )?;
+        let protocol_fee = fees.protocol_fee;
+        let creator_fee = fees.creator_fee;
+        let referral_fee = fees.referral_fee;
+        protocol.record_trade(false, sol_out, protocol_fee, creator_fee)?;
+        
+        // Anti-Sniper: wallets dumping soon after their first buy pay the
+        // launch policy's flip tax on top of the normal fees
+        let policy = meme.launch_policy;
+        let flip_tax = curve::flip_tax(
+            sol_out,
+            policy.flip_tax_bps,
+            policy.flip_tax_window_seconds,
+            ctx.accounts.wallet_state.first_buy_at,
+            meme.created_at,
+            clock.unix_timestamp,
+        )?;
+        let net_sol = sol_out
+            .checked_sub(fees.total()?)
+            .and_then(|sol| sol.checked_sub(flip_tax))
+            .ok_or(ErrorCode::InsufficientFunds)?;
+        
+        // Update reserves
+        meme.virtual_sol_reserves = quote.new_virtual_sol;
+        meme.virtual_token_reserves = quote.new_virtual_token;
+        meme.real_sol_reserves = meme.real_sol_reserves
+            .checked_sub(sol_out)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+        meme.real_token_reserves = meme.real_token_reserves
+            .checked_add(token_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        meme.circulating_supply = meme.circulating_supply
+            .checked_sub(token_amount)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+        meme.total_volume = meme.total_volume
+            .checked_add(sol_out)
+            .ok_or(ErrorCode::Overflow)?;
+        meme.last_trade_timestamp = clock.unix_timestamp;
+        meme.trade_count = meme.trade_count
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        // Return tokens from seller to the vault
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.seller_token_account.to_account_info(),
+                    to: ctx.accounts.token_vault.to_account_info(),
+                    authority: ctx.accounts.seller.to_account_info(),
+                },
+            ),
+            token_amount,
+        )?;
+        
+        // Holder tracking: the seller drops off once their balance hits zero
+        let seller_new_balance = ctx.accounts.seller_token_account.amount
+            .checked_sub(token_amount)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+        let wallet_state = &mut ctx.accounts.wallet_state;
+        if wallet_state.wallet == Pubkey::default() {
+            wallet_state.meme = meme.key();
+            wallet_state.wallet = ctx.accounts.seller.key();
+            wallet_state.bump = ctx.bumps.wallet_state;
+        }
+        record_holder_balance(meme, wallet_state, &mut ctx.accounts.holder_stats, seller_new_balance)?;
+        
+        let spot_price = curve::spot_price(&meme.curve_position())?;
+        ctx.accounts.price_oracle.record_price(spot_price, clock.unix_timestamp);
+        
+        // Release sol_out from the vault, then split it between seller and fees.
+        // The vault is never allowed to dip below its rent-exempt minimum.
+        let vault_info = ctx.accounts.bonding_curve_vault.to_account_info();
+        let rent_minimum = BondingCurveVault::rent_exempt_minimum(&Rent::get()?);
+        let vault_lamports = curve::vault_balance_after_withdrawal(
+            vault_info.lamports(),
+            sol_out,
+            rent_minimum,
+        )?;
+        **vault_info.try_borrow_mut_lamports()? = vault_lamports;
+        credit_lamports(&ctx.accounts.seller.to_account_info(), net_sol)?;
+        
+        // Transfer protocol fee into the treasury
+        if protocol_fee > 0 {
+            let treasury = &mut ctx.accounts.treasury;
+            credit_lamports(&treasury.to_account_info(), protocol_fee)?;
+            treasury.accrue(protocol_fee, &protocol.treasury_split)?;
+        }
+        
+        // Transfer creator fee
+        if creator_fee > 0 {
+            credit_lamports(&ctx.accounts.creator, creator_fee)?;
+        }
+        
+        // Transfer referral fee
+        if let Some(referrer_state) = ctx.accounts.referrer_state.as_mut() {
+            if referral_fee > 0 {
+                credit_lamports(&referrer_state.to_account_info(), referral_fee)?;
+                referrer_state.accrue(referral_fee)?;
+            }
+        }
+        
+        // Route the flip tax to the meme's reward pool, or burn it
+        if flip_tax > 0 {
+            match policy.flip_tax_destination {
+                FlipTaxDestination::RewardPool => {
+                    let reward_pool = &mut ctx.accounts.reward_pool;
+                    credit_lamports(&reward_pool.to_account_info(), flip_tax)?;
+                    reward_pool.total_collected = reward_pool.total_collected
+                        .checked_add(flip_tax)
+                        .ok_or(ErrorCode::Overflow)?;
+                }
+                FlipTaxDestination::Burn => {
+                    let incinerator = ctx.accounts.incinerator
+                        .as_ref()
+                        .ok_or(ErrorCode::InvalidLaunchPolicy)?;
+                    credit_lamports(incinerator, flip_tax)?;
+                }
+            }
+            msg!("Flip tax: {} lamports", flip_tax);
+        }
+        
+        emit!(TradeEvent {
+            meme: meme.key(),
+            trader: ctx.accounts.seller.key(),
+            is_buy: false,
+            sol_amount: sol_out,
+            token_amount,
+            protocol_fee,
+            protocol_fee_bps,
+            creator_fee,
+            referral_fee,
+            flip_tax,
+            virtual_sol_reserves: meme.virtual_sol_reserves,
+            virtual_token_reserves: meme.virtual_token_reserves,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Sell: {} tokens -> {} SOL", token_amount, sol_out);
+        Ok(())
+    }
+
+    // Anti-Bot: commit-reveal launch. During the commit window buyers escrow
+    // SOL against a hidden order size, so there is nothing to front-run and
+    // no advantage in landing first. The escrow has to cover the order and
+    // its fees, and is all that other buyers get to see.
+    pub fn commit_buy(
+        ctx: Context<CommitBuy>,
+        commitment: [u8; 32],
+        escrow_lamports: u64,
+        allowlist: Option<AllowlistEntry>,
+    ) -> Result<()> {
+        let meme = &ctx.accounts.meme;
+        let clock = Clock::get()?;
+        
+        require!(clock.slot < meme.commit_window_end(), ErrorCode::CommitWindowClosed);
+        require!(escrow_lamports > 0, ErrorCode::InvalidAmount);
+        
+        // Anti-Bot: the allowlist holds for batch orders too. The order size
+        // is hidden until reveal, so only the proof is checked here and the
+        // cap is kept on the order for reveal_buy.
+        if let Some(rejection) = allowlist_rejection(
+            meme,
+            &ctx.accounts.buyer.key(),
+            0,
+            0,
+            allowlist.as_ref(),
+            clock.unix_timestamp,
+        ) {
+            return Err(rejection.into());
+        }
+        let allowlist_cap = allowlist
+            .filter(|_| meme.allowlist_active(clock.unix_timestamp))
+            .map(|entry| entry.cap_lamports);
+        
+        // Anti-Bundler: denied wallets can't get into the batch either
+        if let Some(rejection) = denylist_rejection(
+            meme,
+            &ctx.accounts.buyer.key(),
+            ctx.accounts.denylist.as_deref().map(|denylist| &**denylist),
+            clock.unix_timestamp,
+        ) {
+            return Err(rejection.into());
+        }
+        
+        let ix = anchor_lang::solana_program::system_instruction::transfer(
+            &ctx.accounts.buyer.key(),
+            &ctx.accounts.batch_order.key(),
+            escrow_lamports,
+        );
+        anchor_lang::solana_program::program::invoke(
+            &ix,
+            &[
+                ctx.accounts.buyer.to_account_info(),
+                ctx.accounts.batch_order.to_account_info(),
+            ],
+        )?;
+        
+        // Anti-Bundler: launch gate, checked once the escrow is paid
+        if let Some(rejection) = launch_gate_rejection(
+            meme,
+            &ctx.accounts.protocol,
+            ctx.accounts.attestation.as_deref(),
+            ctx.accounts.buyer.lamports(),
+            clock.unix_timestamp,
+        ) {
+            return Err(rejection.into());
+        }
+        
+        let order = &mut ctx.accounts.batch_order;
+        order.meme = meme.key();
+        order.buyer = ctx.accounts.buyer.key();
+        order.commitment = commitment;
+        order.escrow_lamports = escrow_lamports;
+        order.amount = 0;
+        order.protocol_fee_bps = 0;
+        order.revealed = false;
+        order.allowlist_cap = allowlist_cap;
+        order.bump = ctx.bumps.batch_order;
+        
+        let launch_batch = &mut ctx.accounts.launch_batch;
+        launch_batch.order_count = launch_batch.order_count
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        msg!("Launch order committed with {} lamports in escrow", escrow_lamports);
+        Ok(())
+    }
+
+    // Reveal a committed order once the commit window closes. The order
+    // amount moves into the batch; its fees stay escrowed on the order at
+    // the rate locked in here until the order is claimed.
+    pub fn reveal_buy(ctx: Context<RevealBuy>, amount: u64, salt: [u8; 32]) -> Result<()> {
+        let meme = &ctx.accounts.meme;
+        let clock = Clock::get()?;
+        
+        require!(
+            (meme.commit_window_end()..meme.reveal_window_end()).contains(&clock.slot),
+            ErrorCode::RevealWindowClosed
+        );
+        let order = &mut ctx.accounts.batch_order;
+        require!(!order.revealed, ErrorCode::CommitmentMismatch);
+        require!(
+            batch::commitment(&order.buyer, amount, &salt) == order.commitment,
+            ErrorCode::CommitmentMismatch
+        );
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(
+            order.allowlist_cap.is_none_or(|cap| amount <= cap),
+            ErrorCode::AllowlistCapExceeded
+        );
+        // Orders are held to the same cap as a creator's dev-buy; the launch
+        // wallet limit is applied when they're claimed
+        require!(
+            amount <= ctx.accounts.protocol.max_initial_buy_lamports,
+            ErrorCode::DevBuyTooLarge
+        );
+        
+        let (protocol_fee_bps, fees) = current_trade_fees(&ctx.accounts.protocol, meme, clock.slot, amount, false)?;
+        let required = amount
+            .checked_add(fees.total()?)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(order.escrow_lamports >= required, ErrorCode::EscrowTooSmall);
+        
+        let order_info = order.to_account_info();
+        let rent_minimum = Rent::get()?.minimum_balance(8 + BatchOrder::INIT_SPACE);
+        let remaining = curve::vault_balance_after_withdrawal(order_info.lamports(), amount, rent_minimum)?;
+        **order_info.try_borrow_mut_lamports()? = remaining;
+        credit_lamports(&ctx.accounts.launch_batch.to_account_info(), amount)?;
+        
+        order.escrow_lamports -= amount;
+        order.amount = amount;
+        order.protocol_fee_bps = protocol_fee_bps;
+        order.revealed = true;
+        
+        let launch_batch = &mut ctx.accounts.launch_batch;
+        launch_batch.revealed_count = launch_batch.revealed_count
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        launch_batch.total_revealed = launch_batch.total_revealed
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        msg!("Launch order revealed: {} lamports", amount);
+        Ok(())
+    }
+
+    // Once the reveal window closes, anyone can fill the batch: every
+    // revealed order is bought on the curve as a single buy, so all of them
+    // pay the same average price, and normal trading opens
+    pub fn settle_launch_batch(ctx: Context<SettleLaunchBatch>) -> Result<()> {
+        let meme = &mut ctx.accounts.meme;
+        let launch_batch = &mut ctx.accounts.launch_batch;
+        let clock = Clock::get()?;
+        
+        require!(
+            meme.launch_batch_pending
+                && meme.launch_policy.commit_window_slots > 0
+                && clock.slot >= meme.reveal_window_end(),
+            ErrorCode::LaunchBatchNotReady
+        );
+        
+        let remaining_supply = curve::remaining_curve_supply(
+            meme.bonding_curve_supply,
+            meme.circulating_supply,
+        );
+        if launch_batch.total_revealed > 0 && remaining_supply > 0 {
+            // Clamped to the curve allocation: whatever the curve can't fill
+            // is refunded pro-rata when orders are claimed
+            let quote = curve::quote_buy_on(
+                &meme.curve_position(),
+                launch_batch.total_revealed,
+                remaining_supply,
+            )?;
+            fill_launch_batch(meme, launch_batch, &ctx.accounts.bonding_curve_vault.to_account_info(), &quote)?;
+        }
+        finish_launch_batch(
+            meme,
+            &mut ctx.accounts.protocol,
+            launch_batch,
+            &mut ctx.accounts.price_oracle,
+            clock.unix_timestamp,
+        )
+    }
+
+    // Fair launch: during the auction window anyone can bid SOL for the
+    // first tranche of the curve. Bids can be topped up; whatever goes over
+    // the launch's per-wallet cap stays in escrow and is refunded at claim.
+    pub fn place_auction_bid(
+        ctx: Context<PlaceAuctionBid>,
+        amount: u64,
+        allowlist: Option<AllowlistEntry>,
+    ) -> Result<()> {
+        let meme = &ctx.accounts.meme;
+        let clock = Clock::get()?;
+        
+        require!(
+            meme.launch_policy.auction_window_slots > 0 && clock.slot < meme.auction_window_end(),
+            ErrorCode::AuctionWindowClosed
+        );
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        
+        let order = &mut ctx.accounts.batch_order;
+        let is_new_bid = order.buyer == Pubkey::default();
+        if is_new_bid {
+            let protocol_fee_bps = launch_fee_bps(&ctx.accounts.protocol, meme, clock.slot);
+            order.meme = meme.key();
+            order.buyer = ctx.accounts.bidder.key();
+            order.revealed = true; // Auction bids are public
+            order.protocol_fee_bps = protocol_fee_bps;
+            order.bump = ctx.bumps.batch_order;
+        }
+        
+        // Only the part of the bid under the cap counts. Fees on it are
+        // escrowed at the rate locked in by the first bid; the fee on the
+        // whole counted amount is escrowed, not the sum of per-top-up fees.
+        let bid_cap = meme.launch_policy.auction_max_bid_lamports;
+        let counted = order.amount.saturating_add(amount).min(bid_cap);
+        let added = counted - order.amount;
+        let fee_before = curve::trade_fees(order.amount, order.protocol_fee_bps, meme.creator_fee_bps)?.total()?;
+        let fee_after = curve::trade_fees(counted, order.protocol_fee_bps, meme.creator_fee_bps)?.total()?;
+        let escrowed = (amount - added)
+            .checked_add(fee_after - fee_before)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        // Anti-Bot: the allowlist holds for bids too, on the counted amount
+        if let Some(rejection) = allowlist_rejection(
+            meme,
+            &ctx.accounts.bidder.key(),
+            0,
+            counted,
+            allowlist.as_ref(),
+            clock.unix_timestamp,
+        ) {
+            return Err(rejection.into());
+        }
+        if meme.allowlist_active(clock.unix_timestamp) {
+            order.allowlist_cap = allowlist.map(|entry| entry.cap_lamports);
+        }
+        
+        // Anti-Bundler: denied wallets can't bid
+        if let Some(rejection) = denylist_rejection(
+            meme,
+            &ctx.accounts.bidder.key(),
+            ctx.accounts.denylist.as_deref().map(|denylist| &**denylist),
+            clock.unix_timestamp,
+        ) {
+            return Err(rejection.into());
+        }
+        
+        for (to, lamports) in [
+            (ctx.accounts.launch_batch.to_account_info(), added),
+            (order.to_account_info(), escrowed),
+        ] {
+            if lamports == 0 {
+                continue;
+            }
+            let ix = anchor_lang::solana_program::system_instruction::transfer(
+                &ctx.accounts.bidder.key(),
+                &to.key(),
+                lamports,
+            );
+            anchor_lang::solana_program::program::invoke(
+                &ix,
+                &[ctx.accounts.bidder.to_account_info(), to],
+            )?;
+        }
+        
+        // Anti-Bundler: launch gate, checked once the bid is paid
+        if let Some(rejection) = launch_gate_rejection(
+            meme,
+            &ctx.accounts.protocol,
+            ctx.accounts.attestation.as_deref(),
+            ctx.accounts.bidder.lamports(),
+            clock.unix_timestamp,
+        ) {
+            return Err(rejection.into());
+        }
+        
+        order.amount = counted;
+        order.escrow_lamports = order.escrow_lamports
+            .checked_add(escrowed)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        let launch_batch = &mut ctx.accounts.launch_batch;
+        if is_new_bid {
+            launch_batch.order_count = launch_batch.order_count
+                .checked_add(1)
+                .ok_or(ErrorCode::Overflow)?;
+            launch_batch.revealed_count = launch_batch.order_count;
+        }
+        launch_batch.total_revealed = launch_batch.total_revealed
+            .checked_add(added)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        msg!("Auction bid: {} lamports counted, {} over the cap", added, amount - added);
+        Ok(())
+    }
+
+    // After the auction window anyone can settle: the tranche goes pro-rata
+    // to the bids at a single price, and the curve carries on from there.
+    // Bids that don't cover the curve's own price for the whole tranche buy
+    // what they can on the curve instead. Orders are claimed with
+    // claim_batch_order.
+    pub fn settle_launch_auction(ctx: Context<SettleLaunchBatch>) -> Result<()> {
+        let meme = &mut ctx.accounts.meme;
+        let launch_batch = &mut ctx.accounts.launch_batch;
+        let clock = Clock::get()?;
+        
+        require!(
+            meme.launch_batch_pending
+                && meme.launch_policy.auction_window_slots > 0
+                && clock.slot >= meme.auction_window_end(),
+            ErrorCode::LaunchBatchNotReady
+        );
+        
+        if launch_batch.total_revealed > 0 {
+            let tranche = curve::fee_amount(meme.bonding_curve_supply, meme.launch_policy.auction_tranche_bps)?;
+            let fill = batch::clear_auction(
+                meme.virtual_sol_reserves,
+                meme.virtual_token_reserves,
+                launch_batch.total_revealed,
+                tranche,
+            )?;
+            fill_launch_batch(meme, launch_batch, &ctx.accounts.bonding_curve_vault.to_account_info(), &fill)?;
+        }
+        finish_launch_batch(
+            meme,
+            &mut ctx.accounts.protocol,
+            launch_batch,
+            &mut ctx.accounts.price_oracle,
+            clock.unix_timestamp,
+        )
+    }
+
+    // Collect a settled order: its share of the batch's tokens, fees on the
+    // SOL it actually spent, and a refund of everything else. Orders that
+    // were never revealed just get their escrow back.
+    pub fn claim_batch_order(ctx: Context<ClaimBatchOrder>) -> Result<()> {
+        let meme = &mut ctx.accounts.meme;
+        let protocol = &mut ctx.accounts.protocol;
+        let launch_batch = &ctx.accounts.launch_batch;
+        let order = &ctx.accounts.batch_order;
+        let clock = Clock::get()?;
+        
+        require!(launch_batch.settled, ErrorCode::LaunchBatchNotReady);
+        if !order.revealed {
+            msg!("Unrevealed launch order refunded: {} lamports", order.escrow_lamports);
+            return Ok(());
+        }
+        
+        // Anti-Bundler: a wallet denied after it ordered has to wait out the
+        // launch period before it can claim
+        if let Some(rejection) = denylist_rejection(
+            meme,
+            &ctx.accounts.buyer.key(),
+            ctx.accounts.denylist.as_deref().map(|denylist| &**denylist),
+            clock.unix_timestamp,
+        ) {
+            return Err(rejection.into());
+        }
+        
+        let fill = batch::fill_order(
+            order.amount,
+            launch_batch.total_revealed,
+            launch_batch.sol_filled,
+            launch_batch.tokens_filled,
+        )?;
+        
+        // Anti-Whale: batch orders get the same launch wallet cap as a
+        // dev-buy. Tokens over it go back to the curve and the SOL they cost
+        // comes back out of the vault.
+        let launch_cap = curve::fee_amount(meme.total_supply, MAX_WALLET_LAUNCH_BPS)?;
+        let room = launch_cap.saturating_sub(ctx.accounts.buyer_token_account.amount);
+        let (fill, excess) = batch::cap_fill(fill, room);
+        if excess.tokens > 0 {
+            return_to_curve(meme, &ctx.accounts.bonding_curve_vault.to_account_info(), &excess)?;
+            credit_lamports(&ctx.accounts.buyer.to_account_info(), excess.cost)?;
+            msg!("Launch order capped: {} tokens returned to the curve", excess.tokens);
+        }
+        
+        let fees = curve::trade_fees(fill.cost, order.protocol_fee_bps, meme.creator_fee_bps)?;
+        protocol.record_trade(true, fill.cost, fees.protocol_fee, fees.creator_fee)?;
+        
+        // Fees come out of the escrow left on the order, which reveal_buy
+        // checked covers them at the order's full amount
+        let order_info = order.to_account_info();
+        let rent_minimum = Rent::get()?.minimum_balance(8 + BatchOrder::INIT_SPACE);
+        let remaining = curve::vault_balance_after_withdrawal(order_info.lamports(), fees.total()?, rent_minimum)?;
+        **order_info.try_borrow_mut_lamports()? = remaining;
+        if fees.protocol_fee > 0 {
+            credit_lamports(&ctx.accounts.treasury.to_account_info(), fees.protocol_fee)?;
+            ctx.accounts.treasury.accrue(fees.protocol_fee, &protocol.treasury_split)?;
+        }
+        if fees.creator_fee > 0 {
+            credit_lamports(&ctx.accounts.creator.to_account_info(), fees.creator_fee)?;
+            meme.creator_fees_earned = meme.creator_fees_earned
+                .checked_add(fees.creator_fee)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+        
+        // The part of the order the curve couldn't fill comes back out of the batch
+        if fill.refund > 0 {
+            let batch_info = launch_batch.to_account_info();
+            let rent_minimum = Rent::get()?.minimum_balance(8 + LaunchBatch::INIT_SPACE);
+            let remaining = curve::vault_balance_after_withdrawal(batch_info.lamports(), fill.refund, rent_minimum)?;
+            **batch_info.try_borrow_mut_lamports()? = remaining;
+            credit_lamports(&ctx.accounts.buyer.to_account_info(), fill.refund)?;
+        }
+        
+        let meme_key = meme.key();
+        if fill.tokens > 0 {
+            let seeds = &[
+                b"token_vault",
+                meme_key.as_ref(),
+                &[ctx.bumps.token_vault],
+            ];
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.token_vault.to_account_info(),
+                        to: ctx.accounts.buyer_token_account.to_account_info(),
+                        authority: ctx.accounts.token_vault.to_account_info(),
+                    },
+                    &[&seeds[..]],
+                ),
+                fill.tokens,
+            )?;
+        }
+        
+        let buyer_new_balance = ctx.accounts.buyer_token_account.amount
+            .checked_add(fill.tokens)
+            .ok_or(ErrorCode::Overflow)?;
+        let wallet_state = &mut ctx.accounts.wallet_state;
+        if wallet_state.wallet == Pubkey::default() {
+            wallet_state.meme = meme_key;
+            wallet_state.wallet = ctx.accounts.buyer.key();
+            wallet_state.bump = ctx.bumps.wallet_state;
+        }
+        if wallet_state.first_buy_at == 0 {
+            wallet_state.first_buy_at = clock.unix_timestamp;
+        }
+        // An order placed through the allowlist counts against its cap
+        if order.allowlist_cap.is_some() {
+            wallet_state.allowlist_spent = wallet_state.allowlist_spent
+                .checked_add(fill.cost)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+        record_holder_balance(meme, wallet_state, &mut ctx.accounts.holder_stats, buyer_new_balance)?;
+        
+        emit!(TradeEvent {
+            meme: meme_key,
+            trader: ctx.accounts.buyer.key(),
+            is_buy: true,
+            sol_amount: fill.cost,
+            token_amount: fill.tokens,
+            protocol_fee: fees.protocol_fee,
+            protocol_fee_bps: order.protocol_fee_bps,
+            creator_fee: fees.creator_fee,
+            referral_fee: 0,
+            flip_tax: 0,
+            virtual_sol_reserves: meme.virtual_sol_reserves,
+            virtual_token_reserves: meme.virtual_token_reserves,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Launch order claimed: {} lamports -> {} tokens", fill.cost, fill.tokens);
+        Ok(())
+    }
+
+    // Anti-Bundler: the creator denies wallets linked by funding source, see
+    // tools/meme-linkage. Removals apply before additions.
+    pub fn update_denylist(ctx: Context<UpdateDenylist>, add: Vec<Pubkey>, remove: Vec<Pubkey>) -> Result<()> {
+        let denylist = &mut ctx.accounts.denylist;
+        denylist.update(&add, &remove)?;
+        
+        emit!(DenylistUpdated {
+            meme: denylist.meme,
+            added: add.len() as u16,
+            removed: remove.len() as u16,
+            wallets: denylist.wallets.len() as u16,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Anti-Bundler: the attester vouches for a wallet it checked off-chain
+    // (age, history, proof of personhood). Reissuing extends the expiry.
+    pub fn issue_attestation(ctx: Context<IssueAttestation>, expires_at: i64) -> Result<()> {
+        let clock = Clock::get()?;
+        require!(expires_at > clock.unix_timestamp, ErrorCode::InvalidAmount);
+        
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.wallet = ctx.accounts.wallet.key();
+        attestation.attester = ctx.accounts.attester.key();
+        attestation.issued_at = clock.unix_timestamp;
+        attestation.expires_at = expires_at;
+        attestation.bump = ctx.bumps.attestation;
+        
+        msg!("Attested {} until {}", attestation.wallet, expires_at);
+        Ok(())
+    }
+
+    // Closes an attestation, refunding its rent to the attester that issued it
+    pub fn revoke_attestation(ctx: Context<RevokeAttestation>) -> Result<()> {
+        msg!("Revoked attestation for {}", ctx.accounts.attestation.wallet);
+        Ok(())
+    }
+
+    // Open a referral account that buys and sells can credit
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        let referrer_state = &mut ctx.accounts.referrer_state;
+        referrer_state.referrer = ctx.accounts.referrer.key();
+        referrer_state.total_earned = 0;
+        referrer_state.unclaimed = 0;
+        referrer_state.referred_trades = 0;
+        referrer_state.registered_at = Clock::get()?.unix_timestamp;
+        referrer_state.bump = ctx.bumps.referrer_state;
+        
+        msg!("Referrer registered: {}", referrer_state.referrer);
+        Ok(())
+    }
+
+    // Pay out everything a referrer has accrued
+    pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+        let referrer_state = &mut ctx.accounts.referrer_state;
+        let amount = referrer_state.unclaimed;
+        require!(amount > 0, ErrorCode::NothingToClaim);
+        referrer_state.unclaimed = 0;
+        
+        // Accrued fees sit on top of the account's rent-exempt minimum
+        let info = referrer_state.to_account_info();
+        let rent_minimum = Rent::get()?.minimum_balance(8 + ReferrerState::INIT_SPACE);
+        let remaining = curve::vault_balance_after_withdrawal(info.lamports(), amount, rent_minimum)?;
+        **info.try_borrow_mut_lamports()? = remaining;
+        credit_lamports(&ctx.accounts.referrer.to_account_info(), amount)?;
+        
+        msg!("Referral fees claimed: {} lamports", amount);
+        Ok(())
+    }
+
+    // Release whatever part of the creator allocation has vested so far
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        let vesting = &mut ctx.accounts.creator_vesting;
+        let clock = Clock::get()?;
+        
+        let vested = vesting::vested_amount(
+            vesting.total_amount,
+            vesting.start,
+            vesting.cliff_seconds,
+            vesting.duration_seconds,
+            clock.unix_timestamp,
+        );
+        let claimable = vested
+            .checked_sub(vesting.claimed_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(claimable > 0, ErrorCode::NothingToClaim);
+        
+        vesting.claimed_amount = vested;
+        
+        let meme_key = ctx.accounts.meme.key();
+        let seeds = &[
+            b"vesting_vault",
+            meme_key.as_ref(),
+            &[ctx.bumps.vesting_vault],
+        ];
+        let signer = &[&seeds[..]];
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vesting_vault.to_account_info(),
+                    to: ctx.accounts.creator_token_account.to_account_info(),
+                    authority: ctx.accounts.vesting_vault.to_account_info(),
+                },
+                signer,
+            ),
+            claimable,
+        )?;
+        
+        msg!("Claimed {} vested tokens ({} / {})", claimable, vested, vesting.total_amount);
+        Ok(())
+    }
+
+    // Anti-Squatting: Optional registry keyed by the normalized symbol
+    // (case-folded, confusables stripped). A reservation held by a token
+    // that never traded can be taken over once it expires.
+    pub fn reserve_symbol(ctx: Context<ReserveSymbol>, normalized_symbol: String) -> Result<()> {
+        let meme = &ctx.accounts.meme;
+        let clock = Clock::get()?;
+        
+        require!(!normalized_symbol.is_empty(), ErrorCode::InvalidSymbol);
+        require!(
+            symbol::normalize(&meme.symbol) == normalized_symbol,
+            ErrorCode::InvalidSymbol
+        );
+        
+        let reservation = &mut ctx.accounts.symbol_reservation;
+        if reservation.meme != Pubkey::default() {
+            require_keys_neq!(reservation.meme, meme.key(), ErrorCode::SymbolTaken);
+            let holder = ctx.accounts.holder_meme.as_ref().ok_or(ErrorCode::SymbolTaken)?;
+            require_keys_eq!(holder.key(), reservation.meme, ErrorCode::SymbolTaken);
+            
+            let expired = holder.trade_count == 0
+                && clock.unix_timestamp >= reservation.reserved_at + SYMBOL_RESERVATION_TTL_SECONDS;
+            require!(expired, ErrorCode::SymbolTaken);
+            msg!("Symbol reservation for {} expired, reassigning", normalized_symbol);
+        }
+        
+        reservation.meme = meme.key();
+        reservation.creator = meme.creator;
+        reservation.reserved_at = clock.unix_timestamp;
+        reservation.bump = ctx.bumps.symbol_reservation;
+        
+        msg!("Symbol {} reserved for meme {}", normalized_symbol, meme.key());
+        Ok(())
+    }
+
+    // Read-only check that the SOL vault holds its rent-exempt minimum plus
+    // real_sol_reserves. Anyone can donate lamports to the vault, so a
+    // surplus is reported rather than treated as a violation.
+    pub fn verify_vault_invariant(ctx: Context<VerifyVaultInvariant>) -> Result<()> {
+        let meme = &ctx.accounts.meme;
+        let vault_lamports = ctx.accounts.bonding_curve_vault.get_lamports();
+        let rent = BondingCurveVault::rent_exempt_minimum(&Rent::get()?);
+        
+        let expected = curve::expected_vault_lamports(rent, meme.real_sol_reserves)?;
+        require!(vault_lamports >= expected, ErrorCode::VaultInvariantViolated);
+        
+        msg!(
+            "Vault invariant holds: {} lamports (rent {} + reserves {} + surplus {})",
+            vault_lamports,
+            rent,
+            meme.real_sol_reserves,
+            vault_lamports - expected
+        );
+        Ok(())
+    }
+
+    // Read-only quotes for clients to simulate against current state. They
+    // share the pricing, fee and anti-bot logic of the trades, but report
+    // the error a trade would currently hit instead of failing with it.
+    pub fn quote_buy(
+        ctx: Context<QuoteTrade>,
+        sol_amount: u64,
+        allowlist: Option<AllowlistEntry>,
+    ) -> Result<TradeQuote> {
+        let meme = &ctx.accounts.meme;
+        let clock = Clock::get()?;
+        
+        require!(sol_amount > 0, ErrorCode::InvalidAmount);
+        
+        // Graduated and sold-out curves have nothing left to quote
+        let remaining_supply = curve::remaining_curve_supply(
+            meme.bonding_curve_supply,
+            meme.circulating_supply,
+        );
+        if meme.is_graduated || remaining_supply == 0 {
+            let rejection = trade_rejection(meme, clock.unix_timestamp, true).unwrap_or(ErrorCode::CurveSoldOut);
+            return Ok(TradeQuote::rejected(meme, sol_amount, rejection));
+        }
+        let position = meme.curve_position();
+        let quote = curve::quote_buy_on(&position, sol_amount, remaining_supply)?;
+        let (_, fees) = current_trade_fees(
+            &ctx.accounts.protocol,
+            meme,
+            clock.slot,
+            quote.sol_in,
+            ctx.accounts.referrer_state.is_some(),
+        )?;
+        let after = CurvePosition {
+            sold: position.sold + quote.tokens_out,
+            virtual_sol: quote.new_virtual_sol,
+            virtual_token: quote.new_virtual_token,
+            ..position
+        };
+        
+        let balance_after = ctx.accounts.trader_token_account.as_ref().map_or(0, |account| account.amount)
+            .checked_add(quote.tokens_out)
+            .ok_or(ErrorCode::Overflow)?;
+        let over_wallet_limit = balance_after > max_wallet_amount(meme, clock.unix_timestamp)?;
+        let allowlist_spent = ctx.accounts.wallet_state.as_ref().map_or(0, |state| state.allowlist_spent);
+        // The buy pays rent for the wallet state and token account it creates
+        let rent = Rent::get()?;
+        let created_rent = [
+            (ctx.accounts.wallet_state.is_none(), 8 + WalletState::INIT_SPACE),
+            (ctx.accounts.trader_token_account.is_none(), TokenAccount::LEN),
+        ]
+        .into_iter()
+        .filter(|(created, _)| *created)
+        .map(|(_, space)| rent.minimum_balance(space))
+        .sum::<u64>();
+        let lamports_after = ctx.accounts.trader.lamports()
+            .saturating_sub(quote.sol_in)
+            .saturating_sub(fees.total()?)
+            .saturating_sub(created_rent);
+        let rejection = trade_rejection(meme, clock.unix_timestamp, true)
+            .or_else(|| allowlist_rejection(
+                meme,
+                &ctx.accounts.trader.key(),
+                allowlist_spent,
+                quote.sol_in,
+                allowlist.as_ref(),
+                clock.unix_timestamp,
+            ))
+            .or_else(|| denylist_rejection(
+                meme,
+                &ctx.accounts.trader.key(),
+                ctx.accounts.denylist.as_deref().map(|denylist| &**denylist),
+                clock.unix_timestamp,
+            ))
+            .or(over_wallet_limit.then_some(ErrorCode::MaxWalletExceeded))
+            .or(ctx.accounts.self_referral())
+            .or_else(|| launch_gate_rejection(
+                meme,
+                &ctx.accounts.protocol,
+                ctx.accounts.attestation.as_deref(),
+                lamports_after,
+                clock.unix_timestamp,
+            ));
+        
+        Ok(TradeQuote {
+            amount_in: quote.sol_in,
+            amount_out: quote.tokens_out,
+            protocol_fee: fees.protocol_fee,
+            creator_fee: fees.creator_fee,
+            referral_fee: fees.referral_fee,
+            flip_tax: 0,
+            price_impact_bps: curve::price_impact_bps(
+                curve::spot_price(&position)?,
+                curve::spot_price(&after)?,
+            ),
+            virtual_sol_reserves: quote.new_virtual_sol,
+            virtual_token_reserves: quote.new_virtual_token,
+            real_sol_reserves: meme.real_sol_reserves
+                .checked_add(quote.sol_in)
+                .ok_or(ErrorCode::Overflow)?,
+            real_token_reserves: meme.real_token_reserves
+                .checked_sub(quote.tokens_out)
+                .ok_or(ErrorCode::InsufficientFunds)?,
+            rejection: rejection.map(u32::from),
+        })
+    }
+
+    pub fn quote_sell(ctx: Context<QuoteTrade>, token_amount: u64) -> Result<TradeQuote> {
+        let meme = &ctx.accounts.meme;
+        let clock = Clock::get()?;
+        
+        require!(token_amount > 0, ErrorCode::InvalidAmount);
+        if meme.is_graduated {
+            return Ok(TradeQuote::rejected(meme, token_amount, ErrorCode::AlreadyGraduated));
+        }
+        
+        let position = meme.curve_position();
+        let quote = curve::quote_sell_on(&position, token_amount)?;
+        let (_, fees) = current_trade_fees(
+            &ctx.accounts.protocol,
+            meme,
+            clock.slot,
+            quote.sol_out,
+            ctx.accounts.referrer_state.is_some(),
+        )?;
+        let first_buy_at = ctx.accounts.wallet_state.as_ref().map_or(0, |state| state.first_buy_at);
+        let flip_tax = curve::flip_tax(
+            quote.sol_out,
+            meme.launch_policy.flip_tax_bps,
+            meme.launch_policy.flip_tax_window_seconds,
+            first_buy_at,
+            meme.created_at,
+            clock.unix_timestamp,
+        )?;
+        let net_sol = quote.sol_out
+            .checked_sub(fees.total()?)
+            .and_then(|sol| sol.checked_sub(flip_tax))
+            .ok_or(ErrorCode::InsufficientFunds)?;
+        let after = CurvePosition {
+            sold: position.sold - token_amount,
+            virtual_sol: quote.new_virtual_sol,
+            virtual_token: quote.new_virtual_token,
+            ..position
+        };
+        
+        let balance = ctx.accounts.trader_token_account.as_ref().map_or(0, |account| account.amount);
+        let rejection = trade_rejection(meme, clock.unix_timestamp, false)
+            .or(ctx.accounts.self_referral())
+            .or((balance < token_amount).then_some(ErrorCode::InsufficientFunds));
+        
+        Ok(TradeQuote {
+            amount_in: token_amount,
+            amount_out: net_sol,
+            protocol_fee: fees.protocol_fee,
+            creator_fee: fees.creator_fee,
+            referral_fee: fees.referral_fee,
+            flip_tax,
+            price_impact_bps: curve::price_impact_bps(
+                curve::spot_price(&position)?,
+                curve::spot_price(&after)?,
+            ),
+            virtual_sol_reserves: quote.new_virtual_sol,
+            virtual_token_reserves: quote.new_virtual_token,
+            real_sol_reserves: meme.real_sol_reserves
+                .checked_sub(quote.sol_out)
+                .ok_or(ErrorCode::InsufficientFunds)?,
+            real_token_reserves: meme.real_token_reserves
+                .checked_add(token_amount)
+                .ok_or(ErrorCode::Overflow)?,
+            rejection: rejection.map(u32::from),
+        })
+    }
+
+    // Read-only TWAP over at least `window_seconds`, in nanolamports per
+    // whole token, returned to CPI callers as return data
+    pub fn get_twap(ctx: Context<GetTwap>, window_seconds: u32) -> Result<u64> {
+        let twap = ctx.accounts.price_oracle.twap(Clock::get()?.unix_timestamp, window_seconds)?;
+        msg!("TWAP over {}s: {}", window_seconds, twap);
+        Ok(twap)
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+// Books a launch batch's fill on the curve as a single buy, and moves the
+// SOL it spent from the batch escrow into the vault
+fn fill_launch_batch(
+    meme: &mut MemeToken,
+    launch_batch: &mut Account<LaunchBatch>,
+    vault: &AccountInfo,
+    fill: &BuyQuote,
+) -> Result<()> {
+    meme.virtual_sol_reserves = fill.new_virtual_sol;
+    meme.virtual_token_reserves = fill.new_virtual_token;
+    meme.real_sol_reserves = meme.real_sol_reserves
+        .checked_add(fill.sol_in)
+        .ok_or(ErrorCode::Overflow)?;
+    meme.real_token_reserves = meme.real_token_reserves
+        .checked_sub(fill.tokens_out)
+        .ok_or(ErrorCode::InsufficientFunds)?;
+    meme.circulating_supply = meme.circulating_supply
+        .checked_add(fill.tokens_out)
+        .ok_or(ErrorCode::Overflow)?;
+    let reserved = meme.migration_reserve
+        .checked_add(meme.creator_allocation)
+        .ok_or(ErrorCode::Overflow)?;
+    require!(
+        curve::supply_within_cap(meme.total_supply, meme.circulating_supply, reserved),
+        ErrorCode::CurveSupplyExceeded
+    );
+    meme.total_volume = meme.total_volume
+        .checked_add(fill.sol_in)
+        .ok_or(ErrorCode::Overflow)?;
+    meme.trade_count = meme.trade_count
+        .checked_add(1)
+        .ok_or(ErrorCode::Overflow)?;
+    
+    let batch_info = launch_batch.to_account_info();
+    let rent_minimum = Rent::get()?.minimum_balance(8 + LaunchBatch::INIT_SPACE);
+    let remaining = curve::vault_balance_after_withdrawal(batch_info.lamports(), fill.sol_in, rent_minimum)?;
+    **batch_info.try_borrow_mut_lamports()? = remaining;
+    credit_lamports(vault, fill.sol_in)?;
+    
+    launch_batch.sol_filled = fill.sol_in;
+    launch_batch.tokens_filled = fill.tokens_out;
+    Ok(())
+}
+
+// Takes back the part of a batch order over the wallet cap: its tokens
+// return to the curve's reserves and its cost leaves the vault. The
+// virtual reserves move by that same cost, not the curve's sell price, so
+// they stay in step with the real ones.
+fn return_to_curve(meme: &mut MemeToken, vault: &AccountInfo, excess: &batch::OrderFill) -> Result<()> {
+    if meme.curve_type == CurveType::ConstantProduct {
+        meme.virtual_sol_reserves = meme.virtual_sol_reserves
+            .checked_sub(excess.cost)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+        meme.virtual_token_reserves = meme.virtual_token_reserves
+            .checked_add(excess.tokens)
+            .ok_or(ErrorCode::Overflow)?;
+    }
+    meme.real_sol_reserves = meme.real_sol_reserves
+        .checked_sub(excess.cost)
+        .ok_or(ErrorCode::InsufficientFunds)?;
+    meme.real_token_reserves = meme.real_token_reserves
+        .checked_add(excess.tokens)
+        .ok_or(ErrorCode::Overflow)?;
+    meme.circulating_supply = meme.circulating_supply
+        .checked_sub(excess.tokens)
+        .ok_or(ErrorCode::Overflow)?;
+    
+    let rent_minimum = BondingCurveVault::rent_exempt_minimum(&Rent::get()?);
+    let remaining = curve::vault_balance_after_withdrawal(vault.lamports(), excess.cost, rent_minimum)?;
+    **vault.try_borrow_mut_lamports()? = remaining;
+    Ok(())
+}
+
+// Marks a launch batch settled, filled or not, and opens normal trading
+fn finish_launch_batch(
+    meme: &mut MemeToken,
+    protocol: &mut Protocol,
+    launch_batch: &mut LaunchBatch,
+    price_oracle: &mut PriceOracle,
+    now: i64,
+) -> Result<()> {
+    launch_batch.settled = true;
+    meme.launch_batch_pending = false;
+    meme.last_trade_timestamp = now;
+    
+    let spot_price = curve::spot_price(&meme.curve_position())?;
+    price_oracle.record_price(spot_price, now);
+    
+    if meme.circulating_supply == meme.bonding_curve_supply {
+        meme.is_graduated = true;
+        protocol.total_graduations = protocol.total_graduations
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        msg!("Bonding curve sold out - token graduated");
+    }
+    
+    emit!(LaunchBatchSettled {
+        meme: launch_batch.meme,
+        orders: launch_batch.revealed_count,
+        total_revealed: launch_batch.total_revealed,
+        sol_filled: launch_batch.sol_filled,
+        tokens_filled: launch_batch.tokens_filled,
+        timestamp: now,
+    });
+    
+    msg!(
+        "Launch batch settled: {} lamports -> {} tokens",
+        launch_batch.sol_filled,
+        launch_batch.tokens_filled
+    );
+    Ok(())
+}
+
+// Anti-Bot checks that only depend on the clock. Shared with the quote
+// instructions so a quote reports the error the trade would fail with.
+fn trade_rejection(meme: &MemeToken, now: i64, is_buy: bool) -> Option<ErrorCode> {
+    if meme.is_graduated {
+        return Some(ErrorCode::AlreadyGraduated);
+    }
+    if meme.launch_batch_pending {
+        return Some(ErrorCode::LaunchBatchPending);
+    }
+    if now - meme.last_trade_timestamp < TRADE_COOLDOWN_SECONDS {
+        return Some(ErrorCode::TradeTooFast);
+    }
+    if is_buy && now - meme.created_at < LAUNCH_COOLDOWN_SECONDS {
+        return Some(ErrorCode::LaunchCooldownActive);
+    }
+    None
+}
+
+// Anti-Bot: during the allowlist window only wallets in the creator's Merkle
+// tree may buy, each spending at most its cap in curve input
+fn allowlist_rejection(
+    meme: &MemeToken,
+    wallet: &Pubkey,
+    spent: u64,
+    sol_amount: u64,
+    entry: Option<&AllowlistEntry>,
+    now: i64,
+) -> Option<ErrorCode> {
+    if !meme.allowlist_active(now) {
+        return None;
+    }
+    let Some(entry) = entry else {
+        return Some(ErrorCode::NotAllowlisted);
+    };
+    let leaf = allowlist::leaf(wallet, entry.cap_lamports);
+    if !allowlist::verify(&meme.launch_policy.allowlist_root, leaf, &entry.proof) {
+        return Some(ErrorCode::NotAllowlisted);
+    }
+    if spent.saturating_add(sol_amount) > entry.cap_lamports {
+        return Some(ErrorCode::AllowlistCapExceeded);
+    }
+    None
+}
+
+// Anti-Bundler: a creator who opted into a denylist shuts its wallets out
+// for the launch period, when bundled buys matter most
+fn denylist_rejection(
+    meme: &MemeToken,
+    wallet: &Pubkey,
+    denylist: Option<&Denylist>,
+    now: i64,
+) -> Option<ErrorCode> {
+    if !meme.launch_policy.denylist_enabled || now - meme.created_at >= LAUNCH_PERIOD_SECONDS {
+        return None;
+    }
+    match denylist {
+        None => Some(ErrorCode::DenylistMissing),
+        Some(denylist) if denylist.contains(wallet) => Some(ErrorCode::WalletDenylisted),
+        Some(_) => None,
+    }
+}
+
+// Anti-Bundler: throwaway wallets are funded with just enough for the buy,
+// so launch-period buyers can be made to keep a minimum SOL balance and/or
+// hold a current attestation from the protocol attester
+fn launch_gate_rejection(
+    meme: &MemeToken,
+    protocol: &Protocol,
+    attestation: Option<&Attestation>,
+    lamports_after: u64,
+    now: i64,
+) -> Option<ErrorCode> {
+    let policy = &meme.launch_policy;
+    if now - meme.created_at >= LAUNCH_PERIOD_SECONDS {
+        return None;
+    }
+    if lamports_after < policy.min_buyer_balance_lamports {
+        return Some(ErrorCode::BuyerBalanceTooLow);
+    }
+    let attested = attestation.is_some_and(|attestation| attestation.is_valid(&protocol.attester, now));
+    if policy.require_attestation && !attested {
+        return Some(ErrorCode::AttestationRequired);
+    }
+    None
+}
+
+// Anti-Bundler: the most tokens one wallet may hold, tighter during launch
+fn max_wallet_amount(meme: &MemeToken, now: i64) -> Result<u64> {
+    let max_wallet_bps = if now - meme.created_at < LAUNCH_PERIOD_SECONDS {
+        MAX_WALLET_LAUNCH_BPS
+    } else {
+        MAX_WALLET_NORMAL_BPS
+    };
+    Ok((meme.total_supply as u128)
+        .checked_mul(max_wallet_bps as u128)
+        .ok_or(ErrorCode::Overflow)?
+        .checked_div(BASIS_POINTS as u128)
+        .ok_or(ErrorCode::Overflow)? as u64)
+}
+
+// Anti-Snipe: the launch fee decays from when buys open rather than from
+// creation, so the launch cooldown doesn't use up the schedule
+fn launch_fee_bps(protocol: &Protocol, meme: &MemeToken, slot: u64) -> u16 {
+    let buys_open = meme.created_slot.saturating_add(LAUNCH_COOLDOWN_SLOTS);
+    curve::effective_fee_bps(&protocol.fee_schedule, slot.saturating_sub(buys_open))
+}
+
+// Fees on a trade of `amount` lamports at the meme's point on the fee
+// schedule, with the referral share carved out of the protocol fee
+fn current_trade_fees(
+    protocol: &Protocol,
+    meme: &MemeToken,
+    slot: u64,
+    amount: u64,
+    referred: bool,
+) -> Result<(u16, TradeFees)> {
+    let protocol_fee_bps = launch_fee_bps(protocol, meme, slot);
+    let mut fees = curve::trade_fees(amount, protocol_fee_bps, meme.creator_fee_bps)?;
+    if referred {
+        fees = fees.with_referral(protocol.referral_share_bps)?;
+    }
+    Ok((protocol_fee_bps, fees))
+}
+
+// A wallet counts as a holder while the balance seen at its last trade is
+// nonzero, so holders_count only moves on zero <-> positive transitions
+fn record_holder_balance(
+    meme: &mut MemeToken,
+    wallet_state: &mut WalletState,
+    holder_stats: &mut HolderStats,
+    balance: u64,
+) -> Result<()> {
+    match (wallet_state.balance > 0, balance > 0) {
+        (false, true) => {
+            meme.holders_count = meme.holders_count
+                .checked_add(1)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+        (true, false) => {
+            meme.holders_count = meme.holders_count.saturating_sub(1);
+        }
+        _ => {}
+    }
+    wallet_state.balance = balance;
+    
+    holders::record_balance(&mut holder_stats.top_holders, wallet_state.wallet, balance);
+    holder_stats.largest_holder_bps = holders::concentration_bps(&holder_stats.top_holders, 1, meme.total_supply);
+    holder_stats.top_holders_bps = holders::concentration_bps(&holder_stats.top_holders, TOP_HOLDERS, meme.total_supply);
+    Ok(())
+}
+
+// Direct lamport credit for payouts from program-owned accounts
+fn credit_lamports(account: &AccountInfo, amount: u64) -> Result<()> {
+    let mut lamports = account.try_borrow_mut_lamports()?;
+    **lamports = lamports
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    Ok(())
+}
+
+// ============================================================================
+// Account Structs
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeProtocol<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Protocol::INIT_SPACE,
+        seeds = [b"protocol"],
+        bump
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ProtocolTreasury::INIT_SPACE,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, ProtocolTreasury>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawProtocolFees<'info> {
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, ProtocolTreasury>,
+    
+    #[account(mut)]
+    /// CHECK: Any account the authority pays out to
+    pub recipient: AccountInfo<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProtocol<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, symbol: String, uri: String, image_hash: [u8; 32])]
+pub struct CreateMemeToken<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump = protocol.bump
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    // Derived from the protocol-wide counter, not the symbol, so nobody can
+    // own a ticker just by launching it first
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + MemeToken::INIT_SPACE,
+        seeds = [b"meme", protocol.total_memes_created.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub meme: Account<'info, MemeToken>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + ImageRegistry::INIT_SPACE,
+        seeds = [b"image", image_hash.as_ref()],
+        bump
+    )]
+    pub image_registry: Account<'info, ImageRegistry>,
+    
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = DECIMALS,
+        mint::authority = mint,
+        seeds = [b"mint", meme.key().as_ref()],
+        bump
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = creator,
+        token::mint = mint,
+        token::authority = token_vault,
+        seeds = [b"token_vault", meme.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CreatorVesting::INIT_SPACE,
+        seeds = [b"vesting", meme.key().as_ref()],
+        bump
+    )]
+    pub creator_vesting: Option<Account<'info, CreatorVesting>>,
+    
+    #[account(
+        init,
+        payer = creator,
+        token::mint = mint,
+        token::authority = vesting_vault,
+        seeds = [b"vesting_vault", meme.key().as_ref()],
+        bump
+    )]
+    pub vesting_vault: Option<Account<'info, TokenAccount>>,
+    
+    // Only needed with an initial_buy_sol dev-buy
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + WalletState::INIT_SPACE,
+        seeds = [b"wallet", meme.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub creator_wallet_state: Option<Box<Account<'info, WalletState>>>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + BondingCurveVault::INIT_SPACE,
+        seeds = [b"vault", meme.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve_vault: Account<'info, BondingCurveVault>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + HolderStats::INIT_SPACE,
+        seeds = [b"holders", meme.key().as_ref()],
+        bump
+    )]
+    pub holder_stats: Box<Account<'info, HolderStats>>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + RewardPool::INIT_SPACE,
+        seeds = [b"reward_pool", meme.key().as_ref()],
+        bump
+    )]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + PriceOracle::INIT_SPACE,
+        seeds = [b"oracle", meme.key().as_ref()],
+        bump
+    )]
+    pub price_oracle: Box<Account<'info, PriceOracle>>,
+    
+    // Only needed with a commit-reveal or auction LaunchPolicy
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + LaunchBatch::INIT_SPACE,
+        seeds = [b"batch", meme.key().as_ref()],
+        bump
+    )]
+    pub launch_batch: Option<Box<Account<'info, LaunchBatch>>>,
+    
+    // Only needed when the LaunchPolicy enables the denylist
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Denylist::INIT_SPACE,
+        seeds = [b"denylist", meme.key().as_ref()],
+        bump
+    )]
+    pub denylist: Option<Box<Account<'info, Denylist>>>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, ProtocolTreasury>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BuyTokens<'info> {
+    #[account(mut)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut)]
+    pub meme: Account<'info, MemeToken>,
+    
+    #[account(
+        seeds = [b"mint", meme.key().as_ref()],
+        bump
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_vault", meme.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", meme.key().as_ref()],
+        bump = bonding_curve_vault.bump
+    )]
+    pub bonding_curve_vault: Account<'info, BondingCurveVault>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + WalletState::INIT_SPACE,
+        seeds = [b"wallet", meme.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub wallet_state: Account<'info, WalletState>,
+    
+    #[account(
+        mut,
+        seeds = [b"holders", meme.key().as_ref()],
+        bump = holder_stats.bump
+    )]
+    pub holder_stats: Box<Account<'info, HolderStats>>,
+    
+    #[account(
+        mut,
+        seeds = [b"oracle", meme.key().as_ref()],
+        bump = price_oracle.bump
+    )]
+    pub price_oracle: Box<Account<'info, PriceOracle>>,
+    
+    // Required during the launch period if the meme has a denylist
+    #[account(
+        seeds = [b"denylist", meme.key().as_ref()],
+        bump = denylist.bump
+    )]
+    pub denylist: Option<Box<Account<'info, Denylist>>>,
+    
+    // Required during the launch period if the meme requires attestations
+    #[account(
+        seeds = [b"attestation", buyer.key().as_ref()],
+        bump = attestation.bump
+    )]
+    pub attestation: Option<Account<'info, Attestation>>,
+    
+    // Optional: whoever sent the trade, paid a share of the protocol fee
+    #[account(mut)]
+    pub referrer_state: Option<Account<'info, ReferrerState>>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    #[account(mut)]
+    /// CHECK: Creator receives fees
+    pub creator: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, ProtocolTreasury>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SellTokens<'info> {
+    #[account(mut)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut)]
+    pub meme: Account<'info, MemeToken>,
+    
+    #[account(
+        seeds = [b"mint", meme.key().as_ref()],
+        bump
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_vault", meme.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", meme.key().as_ref()],
+        bump = bonding_curve_vault.bump
+    )]
+    pub bonding_curve_vault: Account<'info, BondingCurveVault>,
+    
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = 8 + WalletState::INIT_SPACE,
+        seeds = [b"wallet", meme.key().as_ref(), seller.key().as_ref()],
+        bump
+    )]
+    pub wallet_state: Account<'info, WalletState>,
+    
+    #[account(
+        mut,
+        seeds = [b"holders", meme.key().as_ref()],
+        bump = holder_stats.bump
+    )]
+    pub holder_stats: Box<Account<'info, HolderStats>>,
+    
+    #[account(
+        mut,
+        seeds = [b"oracle", meme.key().as_ref()],
+        bump = price_oracle.bump
+    )]
+    pub price_oracle: Box<Account<'info, PriceOracle>>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_pool", meme.key().as_ref()],
+        bump = reward_pool.bump
+    )]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+    
+    // Only needed when the meme burns its flip tax
+    #[account(mut, address = anchor_lang::solana_program::incinerator::ID)]
+    /// CHECK: Lamports credited here are burned
+    pub incinerator: Option<AccountInfo<'info>>,
+    
+    // Optional: whoever sent the trade, paid a share of the protocol fee
+    #[account(mut)]
+    pub referrer_state: Option<Account<'info, ReferrerState>>,
+    
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    #[account(mut)]
+    /// CHECK: Creator receives fees
+    pub creator: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, ProtocolTreasury>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CommitBuy<'info> {
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    pub meme: Account<'info, MemeToken>,
+    
+    #[account(
+        mut,
+        seeds = [b"batch", meme.key().as_ref()],
+        bump = launch_batch.bump
+    )]
+    pub launch_batch: Account<'info, LaunchBatch>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + BatchOrder::INIT_SPACE,
+        seeds = [b"order", meme.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub batch_order: Account<'info, BatchOrder>,
+    
+    // Required during the launch period if the meme has a denylist
+    #[account(seeds = [b"denylist", meme.key().as_ref()], bump = denylist.bump)]
+    pub denylist: Option<Box<Account<'info, Denylist>>>,
+    
+    // Required during the launch period if the meme requires attestations
+    #[account(seeds = [b"attestation", buyer.key().as_ref()], bump = attestation.bump)]
+    pub attestation: Option<Account<'info, Attestation>>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealBuy<'info> {
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    pub meme: Account<'info, MemeToken>,
+    
+    #[account(
+        mut,
+        seeds = [b"batch", meme.key().as_ref()],
+        bump = launch_batch.bump
+    )]
+    pub launch_batch: Account<'info, LaunchBatch>,
+    
+    #[account(
+        mut,
+        seeds = [b"order", meme.key().as_ref(), buyer.key().as_ref()],
+        bump = batch_order.bump,
+        has_one = buyer @ ErrorCode::Unauthorized
+    )]
+    pub batch_order: Account<'info, BatchOrder>,
+    
+    pub buyer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceAuctionBid<'info> {
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    pub meme: Account<'info, MemeToken>,
+    
+    #[account(
+        mut,
+        seeds = [b"batch", meme.key().as_ref()],
+        bump = launch_batch.bump
+    )]
+    pub launch_batch: Account<'info, LaunchBatch>,
+    
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        space = 8 + BatchOrder::INIT_SPACE,
+        seeds = [b"order", meme.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub batch_order: Account<'info, BatchOrder>,
+    
+    // Required during the launch period if the meme has a denylist
+    #[account(seeds = [b"denylist", meme.key().as_ref()], bump = denylist.bump)]
+    pub denylist: Option<Box<Account<'info, Denylist>>>,
+    
+    // Required during the launch period if the meme requires attestations
+    #[account(seeds = [b"attestation", bidder.key().as_ref()], bump = attestation.bump)]
+    pub attestation: Option<Account<'info, Attestation>>,
+    
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Shared by settle_launch_batch and settle_launch_auction
+#[derive(Accounts)]
+pub struct SettleLaunchBatch<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump = protocol.bump
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut)]
+    pub meme: Account<'info, MemeToken>,
+    
+    #[account(
+        mut,
+        seeds = [b"batch", meme.key().as_ref()],
+        bump = launch_batch.bump
+    )]
+    pub launch_batch: Account<'info, LaunchBatch>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", meme.key().as_ref()],
+        bump = bonding_curve_vault.bump
+    )]
+    pub bonding_curve_vault: Account<'info, BondingCurveVault>,
+    
+    #[account(
+        mut,
+        seeds = [b"oracle", meme.key().as_ref()],
+        bump = price_oracle.bump
+    )]
+    pub price_oracle: Box<Account<'info, PriceOracle>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimBatchOrder<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump = protocol.bump
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut)]
+    pub meme: Account<'info, MemeToken>,
+    
+    #[account(
+        seeds = [b"mint", meme.key().as_ref()],
+        bump
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_vault", meme.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"batch", meme.key().as_ref()],
+        bump = launch_batch.bump
+    )]
+    pub launch_batch: Account<'info, LaunchBatch>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", meme.key().as_ref()],
+        bump = bonding_curve_vault.bump
+    )]
+    pub bonding_curve_vault: Account<'info, BondingCurveVault>,
+    
+    // Closed to the buyer, returning what's left of the escrow
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [b"order", meme.key().as_ref(), buyer.key().as_ref()],
+        bump = batch_order.bump,
+        has_one = buyer @ ErrorCode::Unauthorized
+    )]
+    pub batch_order: Account<'info, BatchOrder>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + WalletState::INIT_SPACE,
+        seeds = [b"wallet", meme.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub wallet_state: Account<'info, WalletState>,
+    
+    #[account(
+        mut,
+        seeds = [b"holders", meme.key().as_ref()],
+        bump = holder_stats.bump
+    )]
+    pub holder_stats: Box<Account<'info, HolderStats>>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, ProtocolTreasury>>,
+    
+    // Required during the launch period if the meme has a denylist
+    #[account(seeds = [b"denylist", meme.key().as_ref()], bump = denylist.bump)]
+    pub denylist: Option<Box<Account<'info, Denylist>>>,
+    
+    #[account(mut, address = meme.creator)]
+    /// CHECK: Creator receives fees
+    pub creator: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDenylist<'info> {
+    #[account(has_one = creator @ ErrorCode::Unauthorized)]
+    pub meme: Account<'info, MemeToken>,
+    
+    #[account(
+        mut,
+        seeds = [b"denylist", meme.key().as_ref()],
+        bump = denylist.bump
+    )]
+    pub denylist: Box<Account<'info, Denylist>>,
+    
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct IssueAttestation<'info> {
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = attester @ ErrorCode::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    /// CHECK: Only the key is attested
+    pub wallet: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = attester,
+        space = 8 + Attestation::INIT_SPACE,
+        seeds = [b"attestation", wallet.key().as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+    
+    #[account(mut)]
+    pub attester: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAttestation<'info> {
+    #[account(
+        mut,
+        close = attester,
+        seeds = [b"attestation", attestation.wallet.as_ref()],
+        bump = attestation.bump,
+        has_one = attester @ ErrorCode::Unauthorized
+    )]
+    pub attestation: Account<'info, Attestation>,
+    
+    #[account(mut)]
+    pub attester: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + ReferrerState::INIT_SPACE,
+        seeds = [b"referrer", referrer.key().as_ref()],
+        bump
+    )]
+    pub referrer_state: Account<'info, ReferrerState>,
+    
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer.key().as_ref()],
+        bump = referrer_state.bump,
+        has_one = referrer @ ErrorCode::Unauthorized
+    )]
+    pub referrer_state: Account<'info, ReferrerState>,
+    
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    pub meme: Account<'info, MemeToken>,
+    
+    #[account(
+        mut,
+        seeds = [b"vesting", meme.key().as_ref()],
+        bump = creator_vesting.bump,
+        has_one = meme,
+        has_one = creator @ ErrorCode::Unauthorized
+    )]
+    pub creator_vesting: Account<'info, CreatorVesting>,
+    
+    #[account(
+        mut,
+        seeds = [b"vesting_vault", meme.key().as_ref()],
+        bump
+    )]
+    pub vesting_vault: Account<'info, TokenAccount>,
+    
+    #[account(address = meme.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(normalized_symbol: String)]
+pub struct ReserveSymbol<'info> {
+    #[account(
+        has_one = creator @ ErrorCode::Unauthorized
+    )]
+    pub meme: Account<'info, MemeToken>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + SymbolReservation::INIT_SPACE,
+        seeds = [b"symbol", normalized_symbol.as_bytes()],
+        bump
+    )]
+    pub symbol_reservation: Account<'info, SymbolReservation>,
+    
+    // Current holder of the reservation, required only to take over an expired one
+    pub holder_meme: Option<Account<'info, MemeToken>>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyVaultInvariant<'info> {
+    pub meme: Account<'info, MemeToken>,
+    
+    #[account(
+        seeds = [b"vault", meme.key().as_ref()],
+        bump = bonding_curve_vault.bump
+    )]
+    pub bonding_curve_vault: Account<'info, BondingCurveVault>,
+}
+
+#[derive(Accounts)]
+pub struct QuoteTrade<'info> {
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    pub meme: Account<'info, MemeToken>,
+    
+    /// CHECK: Only the key is read, to find the trader's balance and wallet state
+    pub trader: UncheckedAccount<'info>,
+    
+    // Optional: omitted when the trader has no token account yet
+    #[account(
+        associated_token::mint = meme.mint,
+        associated_token::authority = trader,
+    )]
+    pub trader_token_account: Option<Account<'info, TokenAccount>>,
+    
+    // Optional: omitted when the trader has never traded this meme
+    #[account(
+        seeds = [b"wallet", meme.key().as_ref(), trader.key().as_ref()],
+        bump = wallet_state.bump
+    )]
+    pub wallet_state: Option<Account<'info, WalletState>>,
+    
+    #[account(
+        seeds = [b"denylist", meme.key().as_ref()],
+        bump = denylist.bump
+    )]
+    pub denylist: Option<Box<Account<'info, Denylist>>>,
+    
+    #[account(
+        seeds = [b"attestation", trader.key().as_ref()],
+        bump = attestation.bump
+    )]
+    pub attestation: Option<Account<'info, Attestation>>,
+    
+    pub referrer_state: Option<Account<'info, ReferrerState>>,
+}
+
+impl QuoteTrade<'_> {
+    fn self_referral(&self) -> Option<ErrorCode> {
+        self.referrer_state
+            .as_ref()
+            .filter(|state| state.referrer == self.trader.key())
+            .map(|_| ErrorCode::SelfReferral)
+    }
+}
+
+#[derive(Accounts)]
+pub struct GetTwap<'info> {
+    pub meme: Account<'info, MemeToken>,
+    
+    #[account(
+        seeds = [b"oracle", meme.key().as_ref()],
+        bump = price_oracle.bump
+    )]
+    pub price_oracle: Box<Account<'info, PriceOracle>>,
+}
+
+// ============================================================================
+// Data Structs
+// ============================================================================
+
+#[account]
+#[derive(InitSpace, Default)]
+pub struct Protocol {
+    pub authority: Pubkey,
+    pub protocol_fee_bps: u16, // Steady-state fee, always fee_schedule.end_bps
+    pub fee_schedule: FeeSchedule,
+    pub creation_fee_lamports: u64,
+    pub graduation_threshold: u64,
+    pub max_initial_buy_lamports: u64, // Cap on a creator's dev-buy at launch
+    pub curve_presets: [CurvePreset; MAX_CURVE_PRESETS],
+    pub curve_preset_count: u8,
+    pub reserve_bounds: ReserveBounds, // Limits for creator-supplied custom reserves
+    pub referral_share_bps: u16, // Share of the protocol fee paid to referrers
+    pub treasury_split: TreasurySplit, // Applied to fees as they reach the treasury
+    pub attester: Pubkey, // Issues Attestations, default when there's none
+    pub total_memes_created: u64,
+    // Protocol-wide statistics, in lamports unless noted
+    pub total_volume: u128, // Buy + sell volume through the curves
+    pub total_buy_volume: u128,
+    pub total_sell_volume: u128,
+    pub total_protocol_fees: u128, // Net of referral shares
+    pub total_creator_fees: u128,
+    pub total_trades: u64,
+    pub total_graduations: u64,
+    pub bump: u8,
+}
+
+impl Protocol {
+    pub fn record_trade(&mut self, is_buy: bool, sol_amount: u64, protocol_fee: u64, creator_fee: u64) -> Result<()> {
+        let volume = if is_buy { &mut self.total_buy_volume } else { &mut self.total_sell_volume };
+        *volume = volume.checked_add(sol_amount as u128).ok_or(ErrorCode::Overflow)?;
+        self.total_volume = self.total_volume
+            .checked_add(sol_amount as u128)
+            .ok_or(ErrorCode::Overflow)?;
+        self.total_protocol_fees = self.total_protocol_fees
+            .checked_add(protocol_fee as u128)
+            .ok_or(ErrorCode::Overflow)?;
+        self.total_creator_fees = self.total_creator_fees
+            .checked_add(creator_fee as u128)
+            .ok_or(ErrorCode::Overflow)?;
+        self.total_trades = self.total_trades
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    /// Virtual `(sol, token)` reserves for a new constant product curve.
+    pub fn resolve_reserves(&self, selection: InitialReserves) -> Result<(u64, u64)> {
+        match selection {
+            InitialReserves::Preset(index) => {
+                require!(index < self.curve_preset_count, ErrorCode::InvalidCurvePreset);
+                let preset = self.curve_presets[index as usize];
+                Ok((preset.virtual_sol_reserves, preset.virtual_token_reserves))
+            }
+            InitialReserves::Custom { virtual_sol_reserves, virtual_token_reserves } => {
+                require!(
+                    self.reserve_bounds.contains(virtual_sol_reserves, virtual_token_reserves),
+                    ErrorCode::ReservesOutOfBounds
+                );
+                Ok((virtual_sol_reserves, virtual_token_reserves))
+            }
+        }
+    }
+}
+
+// Program-owned PDA collecting protocol fees. Lamports above its rent-exempt
+// minimum always equal the sum of the three bucket balances.
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolTreasury {
+    pub team_balance: u64,
+    pub buyback_balance: u64,
+    pub insurance_balance: u64,
+    pub total_collected: u64,
+    pub total_withdrawn: u64,
+    pub bump: u8,
+}
+
+impl ProtocolTreasury {
+    pub fn accrue(&mut self, amount: u64, split: &TreasurySplit) -> Result<()> {
+        let (team, buyback, insurance) = split.allocate(amount)?;
+        self.team_balance = self.team_balance.checked_add(team).ok_or(ErrorCode::Overflow)?;
+        self.buyback_balance = self.buyback_balance.checked_add(buyback).ok_or(ErrorCode::Overflow)?;
+        self.insurance_balance = self.insurance_balance.checked_add(insurance).ok_or(ErrorCode::Overflow)?;
+        self.total_collected = self.total_collected.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug, InitSpace)]
+pub struct TreasurySplit {
+    pub team_bps: u16,
+    pub buyback_bps: u16,
+    pub insurance_bps: u16,
+}
+
+impl TreasurySplit {
+    /// Splits `amount` into `(team, buyback, insurance)`. Rounding dust goes
+    /// to insurance so the parts always add up to `amount`.
+    pub fn allocate(&self, amount: u64) -> Result<(u64, u64, u64)> {
+        let team = curve::fee_amount(amount, self.team_bps)?;
+        let buyback = curve::fee_amount(amount, self.buyback_bps)?;
+        let insurance = amount
+            .checked_sub(team)
+            .and_then(|rest| rest.checked_sub(buyback))
+            .ok_or(ErrorCode::InvalidTreasurySplit)?;
+        Ok((team, buyback, insurance))
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TreasuryBucket {
+    Team,
+    Buyback,
+    Insurance,
+}
+
+// Starting market cap tier for the constant product curve
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug, InitSpace)]
+pub struct CurvePreset {
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug, InitSpace)]
+pub struct ReserveBounds {
+    pub min_virtual_sol: u64,
+    pub max_virtual_sol: u64,
+    pub min_virtual_token: u64,
+    pub max_virtual_token: u64,
+}
+
+impl ReserveBounds {
+    pub fn contains(&self, virtual_sol: u64, virtual_token: u64) -> bool {
+        (self.min_virtual_sol..=self.max_virtual_sol).contains(&virtual_sol)
+            && (self.min_virtual_token..=self.max_virtual_token).contains(&virtual_token)
+    }
+
+    /// Starting prices reserves within these bounds can give, which also
+    /// bound where a shaped curve may start.
+    pub fn price_range(&self) -> std::ops::RangeInclusive<u64> {
+        curve::reserve_price(self.min_virtual_sol, self.max_virtual_token)
+            ..=curve::reserve_price(self.max_virtual_sol, self.min_virtual_token)
+    }
+}
+
+// How create_meme_token picks its starting reserves
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InitialReserves {
+    Preset(u8),
+    Custom {
+        virtual_sol_reserves: u64,
+        virtual_token_reserves: u64,
+    },
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct MemeToken {
+    pub id: u64, // Index from Protocol.total_memes_created, used in the PDA seeds
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    #[max_len(32)]
+    pub name: String,
+    #[max_len(10)]
+    pub symbol: String,
+    #[max_len(200)]
+    pub uri: String,
+    pub image_hash: [u8; 32],
+    pub perceptual_hash: u64, // 64-bit pHash, checked off-chain for near-duplicates
+    pub created_at: i64,
+    pub created_slot: u64, // Launch slot, drives the decaying protocol fee
+    pub total_supply: u64,
+    pub circulating_supply: u64,
+    pub bonding_curve_supply: u64,
+    pub migration_reserve: u64, // Held back from the curve to seed AMM liquidity
+    pub is_graduated: bool,
+    pub amm_migrated: bool,
+    pub amm_type: Option<AmmType>,
+    pub total_volume: u64,
+    pub holders_count: u32, // Changed from u64 to u32 to match IDL
+    pub curve_type: CurveType,
+    pub launch_policy: LaunchPolicy,
+    pub launch_batch_pending: bool, // Trading waits for the commit-reveal batch to settle
+    pub virtual_sol_reserves: u64, // Only used by CurveType::ConstantProduct
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64, // Tokens held in the token vault
+    pub creator_allocation: u64,
+    pub dev_buy_sol: u64,    // SOL the creator spent on the launch dev-buy
+    pub dev_buy_tokens: u64, // Tokens the creator received from it
+    pub creator_fee_bps: u16,
+    pub creator_fees_earned: u64,
+    pub last_trade_timestamp: i64,
+    pub trade_count: u64,
+    pub bump: u8,
+}
+
+impl MemeToken {
+    // Launch batch windows start at creation and are measured in slots
+    pub fn commit_window_end(&self) -> u64 {
+        self.created_slot.saturating_add(self.launch_policy.commit_window_slots)
+    }
+
+    pub fn reveal_window_end(&self) -> u64 {
+        self.commit_window_end().saturating_add(self.launch_policy.reveal_window_slots)
+    }
+
+    pub fn auction_window_end(&self) -> u64 {
+        self.created_slot.saturating_add(self.launch_policy.auction_window_slots)
+    }
+
+    pub fn allowlist_active(&self, now: i64) -> bool {
+        now - self.created_at < self.launch_policy.allowlist_window_seconds
+    }
+
+    pub fn curve_position(&self) -> CurvePosition {
+        CurvePosition {
+            curve_type: self.curve_type,
+            curve_supply: self.bonding_curve_supply,
+            sold: self.circulating_supply,
+            virtual_sol: self.virtual_sol_reserves,
+            virtual_token: self.virtual_token_reserves,
+        }
+    }
+}
+
+// Anti-PVP: one entry per exact image hash, owned by the first launch
+#[account]
+#[derive(InitSpace)]
+pub struct ImageRegistry {
+    pub image_hash: [u8; 32],
+    pub meme: Pubkey,
+    pub creator: Pubkey,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+// Creator allocation locked at launch, released by claim_vested
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorVesting {
+    pub meme: Pubkey,
+    pub creator: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start: i64,
+    pub cliff_seconds: i64,
+    pub duration_seconds: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CreatorVestingParams {
+    pub allocation_bps: u16,
+    pub cliff_seconds: i64,
+    pub duration_seconds: i64,
+}
+
+// Anti-Squatting: keyed by symbol::normalize(symbol)
+#[account]
+#[derive(InitSpace)]
+pub struct SymbolReservation {
+    pub meme: Pubkey,
+    pub creator: Pubkey,
+    pub reserved_at: i64,
+    pub bump: u8,
+}
+
+// Program-owned PDA holding the curve's SOL. Its lamports are always
+// rent-exempt minimum + real_sol_reserves.
+#[account]
+#[derive(InitSpace)]
+pub struct BondingCurveVault {
+    pub meme: Pubkey,
+    pub bump: u8,
+}
+
+impl BondingCurveVault {
+    pub fn rent_exempt_minimum(rent: &Rent) -> u64 {
+        rent.minimum_balance(8 + Self::INIT_SPACE)
+    }
+}
+
+// Creator-chosen launch rules, fixed at creation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug, InitSpace)]
+pub struct LaunchPolicy {
+    pub flip_tax_bps: u16, // 0 disables the early-flip sell tax
+    pub flip_tax_window_seconds: i64, // Measured from the wallet's first buy, or creation
+    pub flip_tax_destination: FlipTaxDestination,
+    pub allowlist_root: [u8; 32], // All zeroes: no allowlist phase
+    pub allowlist_window_seconds: i64, // Measured from creation
+    pub commit_window_slots: u64, // 0 disables commit-reveal buys
+    pub reveal_window_slots: u64,
+    pub auction_window_slots: u64, // 0 disables the batch auction
+    pub auction_tranche_bps: u16,  // Share of the bonding curve supply auctioned
+    pub auction_max_bid_lamports: u64, // Per-wallet cap, the excess is refunded
+    pub denylist_enabled: bool, // Checked against the Denylist during the launch period
+    pub min_buyer_balance_lamports: u64, // Left after a launch-period buy, 0 disables
+    pub require_attestation: bool, // Launch-period buyers need an Attestation
+}
+
+// A buyer's allowlist leaf and its Merkle proof, see allowlist.rs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct AllowlistEntry {
+    pub cap_lamports: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug, InitSpace)]
+pub enum FlipTaxDestination {
+    #[default]
+    RewardPool,
+    Burn,
+}
+
+// Per-meme SOL pool funded by early-flip taxes
+#[account]
+#[derive(InitSpace)]
+pub struct RewardPool {
+    pub meme: Pubkey,
+    pub total_collected: u64,
+    pub bump: u8,
+}
+
+// Totals for a meme's commit-reveal orders or auction bids. Revealed order
+// amounts are held here as lamports until the batch fills on the curve, and
+// anything the curve couldn't fill until the orders are claimed.
+#[account]
+#[derive(InitSpace)]
+pub struct LaunchBatch {
+    pub meme: Pubkey,
+    pub order_count: u32,
+    pub revealed_count: u32,
+    pub total_revealed: u64, // Sum of revealed order amounts
+    pub sol_filled: u64,     // Spent on the curve at settlement
+    pub tokens_filled: u64,
+    pub settled: bool,
+    pub bump: u8,
+}
+
+// One wallet's order in a launch batch
+#[account]
+#[derive(InitSpace)]
+pub struct BatchOrder {
+    pub meme: Pubkey,
+    pub buyer: Pubkey,
+    pub commitment: [u8; 32], // batch::commitment of the hidden amount, unused for bids
+    pub escrow_lamports: u64, // Held on this account on top of rent
+    pub amount: u64,          // Curve input, once revealed; bids count up to the cap
+    pub protocol_fee_bps: u16, // Locked in at reveal
+    pub revealed: bool,
+    pub allowlist_cap: Option<u64>, // Entry cap, for orders placed during the allowlist window
+    pub bump: u8,
+}
+
+// Wallets the creator shuts out of the launch period, kept sorted
+#[account]
+#[derive(InitSpace)]
+pub struct Denylist {
+    pub meme: Pubkey,
+    #[max_len(MAX_DENYLIST_WALLETS)]
+    pub wallets: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl Denylist {
+    pub fn contains(&self, wallet: &Pubkey) -> bool {
+        self.wallets.binary_search(wallet).is_ok()
+    }
+    
+    pub fn update(&mut self, add: &[Pubkey], remove: &[Pubkey]) -> Result<()> {
+        for wallet in remove {
+            if let Ok(i) = self.wallets.binary_search(wallet) {
+                self.wallets.remove(i);
+            }
+        }
+        for wallet in add {
+            if let Err(i) = self.wallets.binary_search(wallet) {
+                self.wallets.insert(i, *wallet);
+            }
+        }
+        require!(self.wallets.len() <= MAX_DENYLIST_WALLETS, ErrorCode::DenylistFull);
+        Ok(())
+    }
+}
+
+// A wallet vouched for by the protocol attester, for any meme's launch gate
+#[account]
+#[derive(InitSpace)]
+pub struct Attestation {
+    pub wallet: Pubkey,
+    pub attester: Pubkey, // Key that issued it, stale once the protocol rotates
+    pub issued_at: i64,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl Attestation {
+    pub fn is_valid(&self, attester: &Pubkey, now: i64) -> bool {
+        *attester != Pubkey::default() && self.attester == *attester && now < self.expires_at
+    }
+}
+
+// Referral earnings, held as lamports on the account until claimed
+#[account]
+#[derive(InitSpace)]
+pub struct ReferrerState {
+    pub referrer: Pubkey,
+    pub total_earned: u64,
+    pub unclaimed: u64,
+    pub referred_trades: u64,
+    pub registered_at: i64,
+    pub bump: u8,
+}
+
+impl ReferrerState {
+    pub fn accrue(&mut self, amount: u64) -> Result<()> {
+        self.total_earned = self.total_earned
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        self.unclaimed = self.unclaimed
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        self.referred_trades = self.referred_trades
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+}
+
+// Per-wallet trading state for a meme
+#[account]
+#[derive(InitSpace)]
+pub struct WalletState {
+    pub meme: Pubkey,
+    pub wallet: Pubkey,
+    pub balance: u64, // Token balance after the wallet's last trade
+    pub first_buy_at: i64, // 0 until the wallet buys from the curve
+    pub allowlist_spent: u64, // Curve input bought during the allowlist window
+    pub bump: u8,
+}
+
+// Largest holders and concentration metrics for the frontend
+#[account]
+#[derive(InitSpace)]
+pub struct HolderStats {
+    pub meme: Pubkey,
+    pub top_holders: [TopHolder; TOP_HOLDERS],
+    pub largest_holder_bps: u16, // Share of total supply held by the top wallet
+    pub top_holders_bps: u16,    // Share of total supply held by the top 10
+    pub bump: u8,
+}
+
+// Returned by quote_buy / quote_sell. Buyers pay amount_in plus the fees on
+// top; sellers receive amount_out with fees and flip tax already taken out.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TradeQuote {
+    pub amount_in: u64,  // Lamports spent on a buy (after clamping), or tokens sold
+    pub amount_out: u64, // Tokens bought, or lamports paid to the seller
+    pub protocol_fee: u64,
+    pub creator_fee: u64,
+    pub referral_fee: u64,
+    pub flip_tax: u64,
+    pub price_impact_bps: u32, // Move in spot price caused by the trade
+    // Reserves after the trade
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub rejection: Option<u32>, // Error code the trade would currently fail with
+}
+
+impl TradeQuote {
+    // A trade that can't happen at all: nothing changes hands and the
+    // reserves are the current ones
+    fn rejected(meme: &MemeToken, amount_in: u64, rejection: ErrorCode) -> Self {
+        TradeQuote {
+            amount_in,
+            amount_out: 0,
+            protocol_fee: 0,
+            creator_fee: 0,
+            referral_fee: 0,
+            flip_tax: 0,
+            price_impact_bps: 0,
+            virtual_sol_reserves: meme.virtual_sol_reserves,
+            virtual_token_reserves: meme.virtual_token_reserves,
+            real_sol_reserves: meme.real_sol_reserves,
+            real_token_reserves: meme.real_token_reserves,
+            rejection: Some(rejection.into()),
+        }
+    }
+}
+
+// TWAP accumulators for other programs to read, see oracle.rs. Prices are
+// spot prices in nanolamports per whole token. Fields are only ever
+// appended so integrators can deserialize the account directly.
+#[account]
+#[derive(InitSpace)]
+pub struct PriceOracle {
+    pub meme: Pubkey,
+    pub price_cumulative: u128, // Sum of price * seconds, wrapping
+    pub last_price: u64,        // Spot price after the latest trade
+    pub last_timestamp: i64,
+    pub observations: [Observation; OBSERVATIONS],
+    pub observation_index: u16, // Newest observation
+    pub observation_count: u16,
+    pub bump: u8,
+}
+
+impl PriceOracle {
+    /// Accrues the previous spot price up to `now`, snapshots the cumulative
+    /// and switches to `price`, the spot price left by the current trade.
+    pub fn record_price(&mut self, price: u64, now: i64) {
+        self.price_cumulative = oracle::accumulate(
+            self.price_cumulative,
+            self.last_price,
+            now - self.last_timestamp,
+        );
+        self.last_timestamp = self.last_timestamp.max(now);
+        oracle::record_observation(
+            &mut self.observations,
+            &mut self.observation_index,
+            &mut self.observation_count,
+            Observation {
+                timestamp: self.last_timestamp,
+                price_cumulative: self.price_cumulative,
+            },
+        );
+        self.last_price = price;
+    }
+
+    pub fn twap(&self, now: i64, window_seconds: u32) -> Result<u64> {
+        let current = Observation {
+            timestamp: now,
+            price_cumulative: oracle::accumulate(
+                self.price_cumulative,
+                self.last_price,
+                now - self.last_timestamp,
+            ),
+        };
+        oracle::twap(
+            &self.observations,
+            self.observation_index,
+            self.observation_count,
+            current,
+            window_seconds,
+        )
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum AmmType {
+    Raydium,
+    Orca,
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+#[event]
+pub struct MemeCreated {
+    pub meme: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub creator_allocation: u64,
+    pub dev_buy_sol: u64,
+    pub dev_buy_tokens: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryWithdrawal {
+    pub bucket: TreasuryBucket,
+    pub amount: u64,
+    pub recipient: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LaunchBatchSettled {
+    pub meme: Pubkey,
+    pub orders: u32,
+    pub total_revealed: u64,
+    pub sol_filled: u64,
+    pub tokens_filled: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DenylistUpdated {
+    pub meme: Pubkey,
+    pub added: u16,
+    pub removed: u16,
+    pub wallets: u16, // Listed after the update
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TradeEvent {
+    pub meme: Pubkey,
+    pub trader: Pubkey,
+    pub is_buy: bool,
+    pub sol_amount: u64, // SOL through the curve, before fees
+    pub token_amount: u64,
+    pub protocol_fee: u64,
+    pub protocol_fee_bps: u16, // Effective fee after launch decay
+    pub creator_fee: u64,
+    pub referral_fee: u64, // Part of the protocol fee paid to the referrer
+    pub flip_tax: u64, // Early-flip surcharge on sells
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub timestamp: i64,
+}
+
+// ============================================================================
+// Error Codes
+// ============================================================================
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Name too long")]
+    NameTooLong,
+    #[msg("Name too short")]
+    NameTooShort,
+    #[msg("Symbol too long")]
+    SymbolTooLong,
+    #[msg("Symbol too short")]
+    SymbolTooShort,
+    #[msg("URI too long")]
+    UriTooLong,
+    #[msg("Already graduated")]
+    AlreadyGraduated,
+    #[msg("Not graduated")]
+    NotGraduated,
+    #[msg("Already migrated")]
+    AlreadyMigrated,
+    #[msg("Slippage exceeded")]
+    SlippageExceeded,
+    #[msg("Duplicate meme")]
+    DuplicateMeme,
+    #[msg("Invalid reserves")]
+    InvalidReserves,
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    #[msg("Overflow")]
+    Overflow,
+    #[msg("Fee too high")]
+    FeeTooHigh,
+    #[msg("Slippage tolerance too high")]
+    SlippageTooHigh,
+    #[msg("Max wallet limit exceeded")]
+    MaxWalletExceeded,
+    #[msg("Trade too fast - wait 1 second")]
+    TradeTooFast,
+    #[msg("Launch cooldown active - wait 60 seconds after token creation")]
+    LaunchCooldownActive,
+    #[msg("Invalid image hash")]
+    InvalidImageHash,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Insufficient funds")]
+    InsufficientFunds,
+    #[msg("Bonding curve allocation sold out")]
+    CurveSoldOut,
+    #[msg("Circulating supply plus migration reserve exceeds total supply")]
+    CurveSupplyExceeded,
+    #[msg("Vault lamports do not cover rent plus real SOL reserves")]
+    VaultInvariantViolated,
+    #[msg("Vault cannot cover this withdrawal and stay rent-exempt")]
+    VaultUnderfunded,
+    #[msg("Symbol is reserved by another token")]
+    SymbolTaken,
+    #[msg("Normalized symbol does not match the token symbol")]
+    InvalidSymbol,
+    #[msg("Symbol must be uppercase ASCII letters and digits")]
+    InvalidSymbolCharacters,
+    #[msg("Name contains control or invisible characters")]
+    InvalidNameCharacters,
+    #[msg("URI must use https://, ipfs:// or ar://")]
+    UnsupportedUriScheme,
+    #[msg("Creator allocation must be between 0.01% and 5% of supply")]
+    CreatorAllocationTooHigh,
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+    #[msg("Nothing vested to claim yet")]
+    NothingToClaim,
+    #[msg("Initial buy exceeds the protocol maximum")]
+    DevBuyTooLarge,
+    #[msg("Invalid bonding curve parameters")]
+    InvalidCurve,
+    #[msg("Unknown curve preset")]
+    InvalidCurvePreset,
+    #[msg("Virtual reserves outside protocol bounds")]
+    ReservesOutOfBounds,
+    #[msg("Launch fee must not be below the steady-state fee")]
+    InvalidFeeSchedule,
+    #[msg("Invalid launch policy")]
+    InvalidLaunchPolicy,
+    #[msg("Traders can't refer themselves")]
+    SelfReferral,
+    #[msg("Treasury split must add up to 100%")]
+    InvalidTreasurySplit,
+    #[msg("Oracle history doesn't cover the requested window")]
+    OracleWindowUnavailable,
+    #[msg("Wallet is not on the launch allowlist")]
+    NotAllowlisted,
+    #[msg("Purchase exceeds the wallet's allowlist cap")]
+    AllowlistCapExceeded,
+    #[msg("Trading opens once the launch batch settles")]
+    LaunchBatchPending,
+    #[msg("Commit window is closed")]
+    CommitWindowClosed,
+    #[msg("Not in the reveal window")]
+    RevealWindowClosed,
+    #[msg("Reveal doesn't match the committed order")]
+    CommitmentMismatch,
+    #[msg("Escrow doesn't cover the order and its fees")]
+    EscrowTooSmall,
+    #[msg("Launch batch can't be settled or claimed yet")]
+    LaunchBatchNotReady,
+    #[msg("Launch auction is closed")]
+    AuctionWindowClosed,
+    #[msg("Wallet is denied during the launch period")]
+    WalletDenylisted,
+    #[msg("Launch-period buys need the meme's denylist account")]
+    DenylistMissing,
+    #[msg("Denylist is full")]
+    DenylistFull,
+    #[msg("Buyer's SOL balance after the buy is below the launch minimum")]
+    BuyerBalanceTooLow,
+    #[msg("Launch-period buys need a valid attestation")]
+    AttestationRequired,
+}
@@ -0,0 +1,77 @@
+// ============================================================================
+// Price Oracle
+// ============================================================================
+//
+// Time-weighted average prices for other programs. Spot reserves can be
+// pushed around and restored inside one transaction, so integrators should
+// read a TWAP instead. Each trade adds the price that held since the previous
+// trade, multiplied by the seconds it held, to a running cumulative (the
+// Uniswap v2 accumulator), and a ring buffer keeps snapshots of that
+// cumulative so a TWAP can be taken over any window the buffer still covers.
+// A price that only exists within a single timestamp contributes nothing.
+
+use anchor_lang::prelude::*;
+
+use crate::ErrorCode;
+
+pub const OBSERVATIONS: usize = 64;
+// At most one snapshot per interval, so a burst of trades can't flush the
+// buffer: 64 snapshots always cover at least an hour of history
+pub const OBSERVATION_INTERVAL_SECONDS: i64 = 60;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug, InitSpace)]
+pub struct Observation {
+    pub timestamp: i64,
+    /// Sum of price * seconds up to `timestamp`, wrapping on overflow
+    pub price_cumulative: u128,
+}
+
+/// `price_cumulative` after `price` has held for `elapsed` seconds. Wraps like
+/// the Uniswap accumulator: only differences between snapshots are meaningful.
+pub fn accumulate(price_cumulative: u128, price: u64, elapsed: i64) -> u128 {
+    let weighted = (price as u128).wrapping_mul(elapsed.max(0) as u128);
+    price_cumulative.wrapping_add(weighted)
+}
+
+/// Writes `observation` into the ring unless the newest snapshot is younger
+/// than OBSERVATION_INTERVAL_SECONDS. `index` is the newest slot and `count`
+/// the number of slots written so far.
+pub fn record_observation(
+    observations: &mut [Observation; OBSERVATIONS],
+    index: &mut u16,
+    count: &mut u16,
+    observation: Observation,
+) {
+    if *count > 0 {
+        let newest = observations[*index as usize];
+        if observation.timestamp - newest.timestamp < OBSERVATION_INTERVAL_SECONDS {
+            return;
+        }
+        *index = ((*index as usize + 1) % OBSERVATIONS) as u16;
+    }
+    observations[*index as usize] = observation;
+    *count = (*count + 1).min(OBSERVATIONS as u16);
+}
+
+/// Average price between the newest snapshot at least `window_seconds` older
+/// than `current` and `current` itself, so the window actually averaged is
+/// never shorter than requested. Fails if the ring doesn't reach back that far.
+pub fn twap(
+    observations: &[Observation; OBSERVATIONS],
+    index: u16,
+    count: u16,
+    current: Observation,
+    window_seconds: u32,
+) -> Result<u64> {
+    require!(window_seconds > 0, ErrorCode::InvalidAmount);
+    let target = current.timestamp - window_seconds as i64;
+
+    let start = (0..count as usize)
+        .map(|age| observations[(index as usize + OBSERVATIONS - age) % OBSERVATIONS])
+        .find(|observation| observation.timestamp <= target)
+        .ok_or(ErrorCode::OracleWindowUnavailable)?;
+
+    let elapsed = (current.timestamp - start.timestamp) as u128;
+    let average = current.price_cumulative.wrapping_sub(start.price_cumulative) / elapsed;
+    u64::try_from(average).map_err(|_| error!(ErrorCode::Overflow))
+}
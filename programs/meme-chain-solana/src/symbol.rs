@@ -0,0 +1,62 @@
+// ============================================================================
+// Symbol Normalization
+// ============================================================================
+//
+// Reservation keys for the symbol registry. "DOGE", "doge", "D0GE" and
+// "DОGE" (Cyrillic О) all normalize to the same key, so a reservation
+// can't be sidestepped with case or lookalike characters.
+
+/// Case-folds `symbol`, maps common confusables onto a single ASCII
+/// skeleton and strips everything else (punctuation, whitespace,
+/// zero-width and other invisible characters).
+pub fn normalize(symbol: &str) -> String {
+    symbol.chars().filter_map(skeleton).collect()
+}
+
+fn skeleton(c: char) -> Option<char> {
+    let c = match c {
+        // Digits that read as letters
+        '0' => 'O',
+        '1' => 'I',
+        '2' => 'Z',
+        '5' => 'S',
+        '8' => 'B',
+        // Cyrillic lookalikes
+        'А' | 'а' => 'A',
+        'В' | 'в' => 'B',
+        'Е' | 'е' | 'Ё' | 'ё' => 'E',
+        'К' | 'к' => 'K',
+        'М' | 'м' => 'M',
+        'Н' | 'н' => 'H',
+        'О' | 'о' => 'O',
+        'Р' | 'р' => 'P',
+        'С' | 'с' => 'C',
+        'Т' | 'т' => 'T',
+        'У' | 'у' => 'Y',
+        'Х' | 'х' => 'X',
+        'І' | 'і' => 'I',
+        'Ѕ' | 'ѕ' => 'S',
+        // Greek lookalikes
+        'Α' | 'α' => 'A',
+        'Β' | 'β' => 'B',
+        'Ε' | 'ε' => 'E',
+        'Ζ' => 'Z',
+        'Η' => 'H',
+        'Ι' | 'ι' => 'I',
+        'Κ' | 'κ' => 'K',
+        'Μ' => 'M',
+        'Ν' => 'N',
+        'Ο' | 'ο' => 'O',
+        'Ρ' | 'ρ' => 'P',
+        'Τ' | 'τ' => 'T',
+        'Υ' | 'υ' => 'Y',
+        'Χ' | 'χ' => 'X',
+        c if c.is_ascii_alphanumeric() => c.to_ascii_uppercase(),
+        _ => return None,
+    };
+    // Letters that read as each other
+    Some(match c {
+        'L' => 'I',
+        c => c,
+    })
+}
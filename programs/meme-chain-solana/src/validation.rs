@@ -0,0 +1,61 @@
+// ============================================================================
+// Metadata Validation
+// ============================================================================
+//
+// Content rules for name / symbol / uri on top of the length checks, so a
+// launch can't spoof a well-known token with invisible characters.
+
+use anchor_lang::prelude::*;
+
+use crate::ErrorCode;
+
+pub const ALLOWED_URI_SCHEMES: [&str; 3] = ["https://", "ipfs://", "ar://"];
+
+/// Symbols are uppercase ASCII letters and digits only.
+pub fn validate_symbol(symbol: &str) -> Result<()> {
+    require!(
+        symbol.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit()),
+        ErrorCode::InvalidSymbolCharacters
+    );
+    Ok(())
+}
+
+/// Names may use any printable Unicode, but no control characters and no
+/// zero-width or bidirectional formatting characters.
+pub fn validate_name(name: &str) -> Result<()> {
+    require!(
+        !name.chars().any(|c| c.is_control() || is_invisible_format(c)),
+        ErrorCode::InvalidNameCharacters
+    );
+    Ok(())
+}
+
+/// URIs must use an allowlisted scheme, have something after it and contain
+/// no whitespace or control characters.
+pub fn validate_uri(uri: &str) -> Result<()> {
+    let rest = ALLOWED_URI_SCHEMES
+        .iter()
+        .find_map(|scheme| uri.strip_prefix(scheme))
+        .ok_or(ErrorCode::UnsupportedUriScheme)?;
+    require!(
+        !rest.is_empty() && !rest.chars().any(|c| c.is_whitespace() || c.is_control() || is_invisible_format(c)),
+        ErrorCode::UnsupportedUriScheme
+    );
+    Ok(())
+}
+
+// Zero-width, joiner and bidi override/isolate characters
+fn is_invisible_format(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}'
+            | '\u{034F}'
+            | '\u{061C}'
+            | '\u{180E}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{2069}'
+            | '\u{FEFF}'
+    )
+}
@@ -0,0 +1,23 @@
+// ============================================================================
+// Creator Vesting Math
+// ============================================================================
+
+/// Tokens unlocked at `now` for a schedule starting at `start`: nothing
+/// before the cliff, then linear from `start` until fully vested at
+/// `start + duration_seconds`.
+pub fn vested_amount(
+    total_amount: u64,
+    start: i64,
+    cliff_seconds: i64,
+    duration_seconds: i64,
+    now: i64,
+) -> u64 {
+    let elapsed = now.saturating_sub(start);
+    if elapsed < cliff_seconds {
+        return 0;
+    }
+    if elapsed >= duration_seconds {
+        return total_amount;
+    }
+    ((total_amount as u128) * (elapsed as u128) / (duration_seconds as u128)) as u64
+}
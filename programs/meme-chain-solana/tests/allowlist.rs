@@ -0,0 +1,53 @@
+use anchor_lang::prelude::Pubkey;
+use meme_chain_solana::allowlist::{leaf, parent, verify, MAX_PROOF_LENGTH};
+
+const SOL: u64 = 1_000_000_000;
+
+fn wallet(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+// Five leaves: ((a b) (c d)) e
+fn tree() -> ([[u8; 32]; 5], [u8; 32]) {
+    let leaves = [
+        leaf(&wallet(1), SOL),
+        leaf(&wallet(2), 2 * SOL),
+        leaf(&wallet(3), SOL),
+        leaf(&wallet(4), 5 * SOL),
+        leaf(&wallet(5), SOL),
+    ];
+    let ab = parent(&leaves[0], &leaves[1]);
+    let cd = parent(&leaves[2], &leaves[3]);
+    let root = parent(&parent(&ab, &cd), &leaves[4]);
+    (leaves, root)
+}
+
+#[test]
+fn proofs_verify_for_every_leaf() {
+    let (leaves, root) = tree();
+    let ab = parent(&leaves[0], &leaves[1]);
+    let cd = parent(&leaves[2], &leaves[3]);
+
+    assert!(verify(&root, leaves[0], &[leaves[1], cd, leaves[4]]));
+    assert!(verify(&root, leaves[3], &[leaves[2], ab, leaves[4]]));
+    assert!(verify(&root, leaves[4], &[parent(&ab, &cd)]));
+}
+
+#[test]
+fn cap_and_wallet_are_bound_to_the_leaf() {
+    let (leaves, root) = tree();
+    let cd = parent(&leaves[2], &leaves[3]);
+    let proof = [leaves[1], cd, leaves[4]];
+
+    assert!(verify(&root, leaf(&wallet(1), SOL), &proof));
+    assert!(!verify(&root, leaf(&wallet(1), 100 * SOL), &proof));
+    assert!(!verify(&root, leaf(&wallet(9), SOL), &proof));
+}
+
+#[test]
+fn overlong_proofs_are_rejected() {
+    let single = leaf(&wallet(1), SOL);
+    let proof = vec![single; MAX_PROOF_LENGTH + 1];
+    let root = proof.iter().fold(single, |node, sibling| parent(&node, sibling));
+    assert!(!verify(&root, single, &proof));
+}
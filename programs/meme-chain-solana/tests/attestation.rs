@@ -0,0 +1,34 @@
+use anchor_lang::prelude::Pubkey;
+use meme_chain_solana::Attestation;
+
+const NOW: i64 = 1_700_000_000;
+
+fn attestation(attester: Pubkey, expires_at: i64) -> Attestation {
+    Attestation {
+        wallet: Pubkey::new_unique(),
+        attester,
+        issued_at: NOW - 60,
+        expires_at,
+        bump: 255,
+    }
+}
+
+#[test]
+fn valid_until_expiry() {
+    let attester = Pubkey::new_unique();
+    let attestation = attestation(attester, NOW + 3_600);
+    assert!(attestation.is_valid(&attester, NOW));
+    assert!(attestation.is_valid(&attester, NOW + 3_599));
+    assert!(!attestation.is_valid(&attester, NOW + 3_600));
+}
+
+#[test]
+fn rotating_the_attester_invalidates_old_attestations() {
+    let old = Pubkey::new_unique();
+    let issued = attestation(old, NOW + 3_600);
+    assert!(!issued.is_valid(&Pubkey::new_unique(), NOW));
+
+    // Clearing the attester doesn't revive attestations issued by nobody
+    let unissued = attestation(Pubkey::default(), NOW + 3_600);
+    assert!(!unissued.is_valid(&Pubkey::default(), NOW));
+}
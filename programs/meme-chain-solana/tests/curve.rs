@@ -0,0 +1,127 @@
+use meme_chain_solana::curve;
+use proptest::prelude::*;
+
+const TOTAL_SUPPLY: u64 = 1_000_000_000 * 1_000_000;
+
+#[derive(Clone, Debug)]
+enum Trade {
+    Buy(u64),
+    Sell(u64),
+}
+
+fn trade() -> impl Strategy<Value = Trade> {
+    prop_oneof![
+        (1u64..=500_000_000_000).prop_map(Trade::Buy),
+        (1u64..=TOTAL_SUPPLY).prop_map(Trade::Sell),
+    ]
+}
+
+/// Minimal mirror of the supply bookkeeping done by buy_tokens / sell_tokens.
+struct CurveState {
+    virtual_sol: u64,
+    virtual_token: u64,
+    circulating: u64,
+    bonding_curve_supply: u64,
+    migration_reserve: u64,
+}
+
+impl CurveState {
+    fn new(virtual_sol: u64, virtual_token: u64) -> Self {
+        let (bonding_curve_supply, migration_reserve) = curve::split_supply(TOTAL_SUPPLY).unwrap();
+        Self {
+            virtual_sol,
+            virtual_token,
+            circulating: 0,
+            bonding_curve_supply,
+            migration_reserve,
+        }
+    }
+
+    fn apply(&mut self, trade: &Trade) {
+        match *trade {
+            Trade::Buy(sol) => {
+                let remaining = curve::remaining_curve_supply(self.bonding_curve_supply, self.circulating);
+                if remaining == 0 {
+                    return;
+                }
+                let Ok(quote) = curve::quote_buy(self.virtual_sol, self.virtual_token, sol, remaining) else {
+                    return;
+                };
+                assert!(quote.sol_in <= sol);
+                self.virtual_sol = quote.new_virtual_sol;
+                self.virtual_token = quote.new_virtual_token;
+                self.circulating += quote.tokens_out;
+            }
+            Trade::Sell(tokens) => {
+                let tokens = tokens.min(self.circulating);
+                if tokens == 0 {
+                    return;
+                }
+                let quote = curve::quote_sell(self.virtual_sol, self.virtual_token, tokens).unwrap();
+                self.virtual_sol = quote.new_virtual_sol;
+                self.virtual_token = quote.new_virtual_token;
+                self.circulating -= tokens;
+            }
+        }
+    }
+}
+
+#[test]
+fn split_supply_reserves_twenty_percent_for_migration() {
+    let (curve_supply, reserve) = curve::split_supply(TOTAL_SUPPLY).unwrap();
+    assert_eq!(curve_supply, TOTAL_SUPPLY / 10 * 8);
+    assert_eq!(reserve, TOTAL_SUPPLY / 10 * 2);
+    assert_eq!(curve_supply + reserve, TOTAL_SUPPLY);
+}
+
+#[test]
+fn price_impact_is_relative_to_the_price_before() {
+    assert_eq!(curve::price_impact_bps(1_000, 1_050), 500);
+    assert_eq!(curve::price_impact_bps(1_000, 900), 1_000);
+    // Moves bigger than 100% are reported in full
+    assert_eq!(curve::price_impact_bps(1_000, 5_000), 40_000);
+    assert_eq!(curve::price_impact_bps(0, 5_000), 0);
+
+    // A 1 SOL buy at launch reserves moves the constant product price ~6.8%
+    let quote = curve::quote_buy(30_000_000_000, 1_073_000_000_000_000, 1_000_000_000, TOTAL_SUPPLY).unwrap();
+    let before = 30_000_000_000u128 * 1_000_000_000_000_000 / 1_073_000_000_000_000;
+    let after = quote.new_virtual_sol as u128 * 1_000_000_000_000_000 / quote.new_virtual_token as u128;
+    assert_eq!(curve::price_impact_bps(before as u64, after as u64), 677);
+}
+
+#[test]
+fn buy_is_clamped_to_remaining_allocation() {
+    // Virtual token reserves far above the allocation so a whale buy would overshoot
+    let quote = curve::quote_buy(30_000_000_000, 10 * TOTAL_SUPPLY, u64::MAX / 4, 1_000).unwrap();
+    assert_eq!(quote.tokens_out, 1_000);
+    assert!(quote.sol_in < u64::MAX / 4);
+    assert!(curve::tokens_out_for_sol(30_000_000_000, 10 * TOTAL_SUPPLY, quote.sol_in).unwrap() >= 1_000);
+}
+
+proptest! {
+    #[test]
+    fn supply_cap_holds_for_any_trade_sequence(
+        virtual_sol in 1_000_000_000u64..=100_000_000_000,
+        virtual_token in TOTAL_SUPPLY..=10 * TOTAL_SUPPLY,
+        trades in proptest::collection::vec(trade(), 1..64),
+    ) {
+        let mut state = CurveState::new(virtual_sol, virtual_token);
+        for trade in &trades {
+            state.apply(trade);
+            prop_assert!(state.circulating <= state.bonding_curve_supply);
+            prop_assert!(curve::supply_within_cap(TOTAL_SUPPLY, state.circulating, state.migration_reserve));
+        }
+    }
+
+    #[test]
+    fn clamped_buy_never_costs_more_than_requested(
+        virtual_sol in 1u64..=100_000_000_000,
+        virtual_token in 2u64..=10 * TOTAL_SUPPLY,
+        sol_amount in 1u64..=1_000_000_000_000,
+        remaining in 1u64..=TOTAL_SUPPLY,
+    ) {
+        let quote = curve::quote_buy(virtual_sol, virtual_token, sol_amount, remaining).unwrap();
+        prop_assert!(quote.sol_in <= sol_amount);
+        prop_assert!(quote.tokens_out <= remaining);
+    }
+}
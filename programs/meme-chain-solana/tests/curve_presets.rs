@@ -0,0 +1,55 @@
+use meme_chain_solana::{CurvePreset, InitialReserves, Protocol, ReserveBounds};
+
+const SOL: u64 = 1_000_000_000;
+const TOKENS: u64 = 1_000_000;
+
+fn protocol() -> Protocol {
+    let mut curve_presets = [CurvePreset::default(); 4];
+    curve_presets[0] = CurvePreset {
+        virtual_sol_reserves: 30 * SOL,
+        virtual_token_reserves: 1_073_000_000 * TOKENS,
+    };
+    curve_presets[1] = CurvePreset {
+        virtual_sol_reserves: 60 * SOL,
+        virtual_token_reserves: 1_073_000_000 * TOKENS,
+    };
+    Protocol {
+        curve_presets,
+        curve_preset_count: 2,
+        reserve_bounds: ReserveBounds {
+            min_virtual_sol: 10 * SOL,
+            max_virtual_sol: 500 * SOL,
+            min_virtual_token: 900_000_000 * TOKENS,
+            max_virtual_token: 2_000_000_000 * TOKENS,
+        },
+        ..Protocol::default()
+    }
+}
+
+#[test]
+fn presets_resolve_by_index() {
+    let protocol = protocol();
+    assert_eq!(
+        protocol.resolve_reserves(InitialReserves::Preset(1)).unwrap(),
+        (60 * SOL, 1_073_000_000 * TOKENS)
+    );
+    // Unused slots beyond curve_preset_count are not selectable
+    assert!(protocol.resolve_reserves(InitialReserves::Preset(2)).is_err());
+}
+
+#[test]
+fn custom_reserves_must_stay_within_bounds() {
+    let protocol = protocol();
+    let custom = |virtual_sol_reserves, virtual_token_reserves| {
+        protocol.resolve_reserves(InitialReserves::Custom {
+            virtual_sol_reserves,
+            virtual_token_reserves,
+        })
+    };
+
+    assert!(custom(45 * SOL, 1_000_000_000 * TOKENS).is_ok());
+    // 1 lamport against 1B tokens: the trap price this guards against
+    assert!(custom(1, 1_000_000_000 * TOKENS).is_err());
+    assert!(custom(501 * SOL, 1_000_000_000 * TOKENS).is_err());
+    assert!(custom(45 * SOL, 100 * TOKENS).is_err());
+}
@@ -0,0 +1,269 @@
+use meme_chain_solana::curve::{
+    self, buy_cost, curve_cost, sell_proceeds, tokens_for_sol, CurvePosition, CurveSegment, CurveType,
+};
+use proptest::prelude::*;
+use std::ops::RangeInclusive;
+
+const CURVE_SUPPLY: u64 = 800_000_000 * 1_000_000;
+const NANOLAMPORTS: u64 = 1_000_000_000;
+
+fn linear() -> CurveType {
+    CurveType::Linear {
+        start_price: 28 * NANOLAMPORTS,
+        end_price: 400 * NANOLAMPORTS,
+    }
+}
+
+fn exponential() -> CurveType {
+    CurveType::Exponential {
+        start_price: 28 * NANOLAMPORTS,
+        doublings: 4,
+    }
+}
+
+fn piecewise() -> CurveType {
+    let mut segments = [CurveSegment::default(); curve::MAX_CURVE_SEGMENTS];
+    segments[0] = CurveSegment { end_bps: 2_500, end_price: 40 * NANOLAMPORTS };
+    segments[1] = CurveSegment { end_bps: 7_500, end_price: 40 * NANOLAMPORTS };
+    segments[2] = CurveSegment { end_bps: 10_000, end_price: 900 * NANOLAMPORTS };
+    CurveType::Piecewise {
+        start_price: 10 * NANOLAMPORTS,
+        segments,
+        segment_count: 3,
+    }
+}
+
+// Starting prices the default reserve bounds allow: 10 SOL / 2B tokens up
+// to 500 SOL / 0.9B tokens
+fn start_prices() -> RangeInclusive<u64> {
+    curve::reserve_price(10 * NANOLAMPORTS, 2_000_000_000_000_000)
+        ..=curve::reserve_price(500 * NANOLAMPORTS, 900_000_000_000_000)
+}
+
+fn linear_shape() -> impl Strategy<Value = CurveType> {
+    start_prices()
+        .prop_flat_map(|start_price| (Just(start_price), start_price..=start_price * 1_000))
+        .prop_map(|(start_price, end_price)| CurveType::Linear { start_price, end_price })
+}
+
+fn exponential_shape() -> impl Strategy<Value = CurveType> {
+    (start_prices(), 1..=curve::MAX_EXPONENTIAL_DOUBLINGS)
+        .prop_map(|(start_price, doublings)| CurveType::Exponential { start_price, doublings })
+}
+
+// Up to MAX_CURVE_SEGMENTS segments between random breakpoints, each rising
+// by a random amount or staying flat
+fn piecewise_shape() -> impl Strategy<Value = CurveType> {
+    (
+        start_prices(),
+        prop::collection::btree_set(1u16..10_000, 0..curve::MAX_CURVE_SEGMENTS),
+        prop::collection::vec(0..=100 * NANOLAMPORTS, curve::MAX_CURVE_SEGMENTS),
+    )
+        .prop_map(|(start_price, breakpoints, rises)| {
+            let ends: Vec<u16> = breakpoints.into_iter().chain([10_000]).collect();
+            let mut segments = [CurveSegment::default(); curve::MAX_CURVE_SEGMENTS];
+            let mut end_price = start_price;
+            for (segment, (&end_bps, rise)) in segments.iter_mut().zip(ends.iter().zip(rises)) {
+                end_price += rise;
+                *segment = CurveSegment { end_bps, end_price };
+            }
+            CurveType::Piecewise { start_price, segments, segment_count: ends.len() as u8 }
+        })
+}
+
+fn shape() -> impl Strategy<Value = CurveType> {
+    prop_oneof![linear_shape(), exponential_shape(), piecewise_shape()]
+}
+
+#[test]
+fn shapes_validate() {
+    for shape in [CurveType::ConstantProduct, linear(), exponential(), piecewise()] {
+        curve::validate_curve_type(&shape).unwrap();
+    }
+
+    let falling = CurveType::Linear { start_price: 10, end_price: 9 };
+    let flat_exponential = CurveType::Exponential { start_price: 10, doublings: 0 };
+    let CurveType::Piecewise { start_price, mut segments, .. } = piecewise() else {
+        unreachable!()
+    };
+    segments[2].end_bps = 9_999;
+    let short = CurveType::Piecewise { start_price, segments, segment_count: 3 };
+    for shape in [falling, flat_exponential, short] {
+        assert!(curve::validate_curve_type(&shape).is_err());
+    }
+}
+
+#[test]
+fn shapes_are_bounded_by_the_protocol() {
+    let start_prices = start_prices();
+    assert_eq!(*start_prices.start(), 5 * NANOLAMPORTS);
+    for shape in [CurveType::ConstantProduct, linear(), exponential(), piecewise()] {
+        curve::validate_curve_bounds(&shape, CURVE_SUPPLY, start_prices.clone()).unwrap();
+    }
+
+    let too_cheap = CurveType::Linear { start_price: NANOLAMPORTS, end_price: 400 * NANOLAMPORTS };
+    let too_dear = CurveType::Exponential { start_price: 600 * NANOLAMPORTS, doublings: 1 };
+    for shape in [too_cheap, too_dear] {
+        assert!(curve::validate_curve_bounds(&shape, CURVE_SUPPLY, start_prices.clone()).is_err());
+    }
+
+    // Even with no price bounds, buying out the curve has to fit in a u64
+    let runaway = CurveType::Exponential { start_price: u64::MAX / 2, doublings: 10 };
+    curve::validate_curve_type(&runaway).unwrap();
+    assert!(curve_cost(&runaway, CURVE_SUPPLY, CURVE_SUPPLY).is_err());
+    assert!(curve::validate_curve_bounds(&runaway, CURVE_SUPPLY, 0..=u64::MAX).is_err());
+}
+
+#[test]
+fn full_curve_costs_match_closed_forms() {
+    // Linear: average of start and end price over the whole supply
+    let expected = (28 + 400) * CURVE_SUPPLY / 2 / 1_000_000;
+    let linear_total = curve_cost(&linear(), CURVE_SUPPLY, CURVE_SUPPLY).unwrap();
+    assert!(linear_total.abs_diff(expected) <= 1);
+
+    // Exponential: P0 * S * (2^d - 1) / (d * ln 2)
+    let expected = 28.0 * (CURVE_SUPPLY as f64 / 1e6) * 15.0 / (4.0 * std::f64::consts::LN_2);
+    let exponential_total = curve_cost(&exponential(), CURVE_SUPPLY, CURVE_SUPPLY).unwrap();
+    assert!((exponential_total as f64 - expected).abs() / expected < 1e-9);
+
+    // Marginal price at the end of the exponential curve is 2^4 times the start
+    let chunk = 1_000_000_000;
+    let first = buy_cost(&exponential(), CURVE_SUPPLY, 0, chunk).unwrap();
+    let last = buy_cost(&exponential(), CURVE_SUPPLY, CURVE_SUPPLY - chunk, chunk).unwrap();
+    assert!((last as f64 / first as f64 - 16.0).abs() < 0.01);
+}
+
+#[test]
+fn constant_product_dispatches_to_virtual_reserves() {
+    let position = CurvePosition {
+        curve_type: CurveType::ConstantProduct,
+        curve_supply: CURVE_SUPPLY,
+        sold: 0,
+        virtual_sol: 30_000_000_000,
+        virtual_token: 1_073_000_000_000_000,
+    };
+    let expected = curve::quote_buy(30_000_000_000, 1_073_000_000_000_000, 1_000_000_000, CURVE_SUPPLY).unwrap();
+    assert_eq!(curve::quote_buy_on(&position, 1_000_000_000, CURVE_SUPPLY).unwrap(), expected);
+}
+
+#[test]
+fn spot_price_is_the_marginal_price() {
+    let at = |curve_type, sold| {
+        curve::spot_price(&CurvePosition {
+            curve_type,
+            curve_supply: CURVE_SUPPLY,
+            sold,
+            virtual_sol: 30_000_000_000,
+            virtual_token: 1_073_000_000_000_000,
+        })
+        .unwrap()
+    };
+    assert_eq!(at(linear(), 0), 28 * NANOLAMPORTS);
+    assert_eq!(at(linear(), CURVE_SUPPLY / 2), 214 * NANOLAMPORTS);
+    assert_eq!(at(linear(), CURVE_SUPPLY), 400 * NANOLAMPORTS);
+    assert_eq!(at(exponential(), CURVE_SUPPLY), 16 * 28 * NANOLAMPORTS);
+    assert_eq!(at(piecewise(), CURVE_SUPPLY / 2), 40 * NANOLAMPORTS);
+    // 30 SOL over 1.073B tokens, just under 28 lamports per token
+    assert_eq!(at(CurveType::ConstantProduct, 0), 27_958_993_476);
+
+    // Matches what a small buy actually pays
+    let chunk = 1_000_000;
+    let paid = buy_cost(&exponential(), CURVE_SUPPLY, CURVE_SUPPLY / 3, chunk).unwrap();
+    let quoted = at(exponential(), CURVE_SUPPLY / 3);
+    assert!((paid * NANOLAMPORTS).abs_diff(quoted) <= NANOLAMPORTS);
+}
+
+proptest! {
+    #[test]
+    fn generated_shapes_are_valid(shape in shape()) {
+        prop_assert!(curve::validate_curve_type(&shape).is_ok());
+        prop_assert!(curve::validate_curve_bounds(&shape, CURVE_SUPPLY, start_prices()).is_ok());
+    }
+
+    #[test]
+    fn cost_is_monotone(shape in shape(), a in 0..=CURVE_SUPPLY, b in 0..=CURVE_SUPPLY, step in 1u64..1_000) {
+        let (lo, hi) = (a.min(b), a.max(b));
+        prop_assert!(curve_cost(&shape, CURVE_SUPPLY, lo).unwrap() <= curve_cost(&shape, CURVE_SUPPLY, hi).unwrap());
+
+        // Also between neighbouring values, where fixed-point rounding would show up
+        let next = (lo + step).min(CURVE_SUPPLY);
+        prop_assert!(curve_cost(&shape, CURVE_SUPPLY, lo).unwrap() <= curve_cost(&shape, CURVE_SUPPLY, next).unwrap());
+    }
+
+    #[test]
+    fn price_never_falls(shape in shape(), a in 0..CURVE_SUPPLY / 2, b in 0..CURVE_SUPPLY / 2, n in 1u64..1_000_000_000_000) {
+        let (lo, hi) = (a.min(b), a.max(b));
+        // One lamport of slack for flooring at each end
+        prop_assert!(buy_cost(&shape, CURVE_SUPPLY, lo, n).unwrap() <= buy_cost(&shape, CURVE_SUPPLY, hi, n).unwrap() + 1);
+    }
+
+    #[test]
+    fn trades_are_path_independent(shape in shape(), sold in 0..CURVE_SUPPLY / 2, a in 0..CURVE_SUPPLY / 4, b in 0..CURVE_SUPPLY / 4) {
+        let split = buy_cost(&shape, CURVE_SUPPLY, sold, a).unwrap()
+            + buy_cost(&shape, CURVE_SUPPLY, sold + a, b).unwrap();
+        prop_assert_eq!(split, buy_cost(&shape, CURVE_SUPPLY, sold, a + b).unwrap());
+
+        // Selling back what was bought returns exactly what was paid
+        let top = sold + a + b;
+        let sold_back = sell_proceeds(&shape, CURVE_SUPPLY, top, a).unwrap()
+            + sell_proceeds(&shape, CURVE_SUPPLY, top - a, b).unwrap();
+        prop_assert_eq!(sold_back, split);
+    }
+
+    #[test]
+    fn tokens_for_sol_inverts_buy_cost(shape in shape(), sold in 0..CURVE_SUPPLY, sol in 1u64..200_000_000_000) {
+        let remaining = CURVE_SUPPLY - sold;
+        let tokens = tokens_for_sol(&shape, CURVE_SUPPLY, sold, sol, remaining).unwrap();
+        prop_assert!(buy_cost(&shape, CURVE_SUPPLY, sold, tokens).unwrap() <= sol);
+        if tokens < remaining {
+            prop_assert!(buy_cost(&shape, CURVE_SUPPLY, sold, tokens + 1).unwrap() > sol);
+        }
+    }
+
+    #[test]
+    fn curve_always_holds_its_cumulative_cost(
+        shape in shape(),
+        trades in prop::collection::vec((any::<bool>(), 1u64..50_000_000_000), 1..40),
+    ) {
+        let mut position = CurvePosition {
+            curve_type: shape,
+            curve_supply: CURVE_SUPPLY,
+            sold: 0,
+            virtual_sol: 0,
+            virtual_token: 0,
+        };
+        let mut reserves = 0u64;
+        for (is_buy, amount) in trades {
+            if is_buy {
+                let remaining = CURVE_SUPPLY - position.sold;
+                let Ok(quote) = curve::quote_buy_on(&position, amount, remaining) else { continue };
+                prop_assert!(quote.sol_in <= amount);
+                reserves += quote.sol_in;
+                position.sold += quote.tokens_out;
+            } else {
+                let tokens = (amount * 1_000).min(position.sold);
+                let quote = curve::quote_sell_on(&position, tokens).unwrap();
+                reserves -= quote.sol_out;
+                position.sold -= tokens;
+            }
+            prop_assert_eq!(reserves, curve_cost(&shape, CURVE_SUPPLY, position.sold).unwrap());
+        }
+    }
+}
+
+proptest! {
+    // The constant product curve rounds each trade in its own favour, so
+    // splitting a buy can only cost the buyer dust, never gain them tokens
+    #[test]
+    fn constant_product_is_path_independent_up_to_rounding(a in 1u64..20_000_000_000, b in 1u64..20_000_000_000) {
+        let (vs, vt) = (30_000_000_000u64, 1_073_000_000_000_000u64);
+        let first = curve::quote_buy(vs, vt, a, CURVE_SUPPLY).unwrap();
+        let second = curve::quote_buy(first.new_virtual_sol, first.new_virtual_token, b, CURVE_SUPPLY).unwrap();
+        let whole = curve::quote_buy(vs, vt, a + b, CURVE_SUPPLY).unwrap();
+
+        let split = first.tokens_out + second.tokens_out;
+        prop_assert!(split <= whole.tokens_out);
+        prop_assert!(whole.tokens_out - split <= 1);
+        prop_assert!(second.tokens_out <= curve::quote_buy(vs, vt, b, CURVE_SUPPLY).unwrap().tokens_out);
+    }
+}
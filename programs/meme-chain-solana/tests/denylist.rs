@@ -0,0 +1,45 @@
+use anchor_lang::prelude::Pubkey;
+use meme_chain_solana::Denylist;
+
+fn wallet(n: u16) -> Pubkey {
+    let mut bytes = [0xff; 32];
+    bytes[..2].copy_from_slice(&n.to_be_bytes());
+    Pubkey::new_from_array(bytes)
+}
+
+fn denylist() -> Denylist {
+    Denylist { meme: Pubkey::default(), wallets: Vec::new(), bump: 0 }
+}
+
+#[test]
+fn updates_keep_the_list_sorted_and_unique() {
+    let mut list = denylist();
+    list.update(&[wallet(3), wallet(1), wallet(2), wallet(1)], &[]).unwrap();
+    assert_eq!(list.wallets, [wallet(1), wallet(2), wallet(3)]);
+    assert!(list.contains(&wallet(2)));
+    assert!(!list.contains(&wallet(4)));
+
+    list.update(&[wallet(0)], &[wallet(2), wallet(9)]).unwrap();
+    assert_eq!(list.wallets, [wallet(0), wallet(1), wallet(3)]);
+    assert!(!list.contains(&wallet(2)));
+}
+
+#[test]
+fn removals_apply_before_additions() {
+    let mut list = denylist();
+    list.update(&[wallet(1)], &[]).unwrap();
+    list.update(&[wallet(1)], &[wallet(1)]).unwrap();
+    assert!(list.contains(&wallet(1)));
+}
+
+#[test]
+fn list_is_capped() {
+    let mut list = denylist();
+    let wallets: Vec<Pubkey> = (0..200).map(wallet).collect();
+    list.update(&wallets, &[]).unwrap();
+    assert!(list.update(&[wallet(200)], &[]).is_err());
+
+    // Swapping one out for another still fits
+    list.update(&[wallet(200)], &[wallet(0)]).unwrap();
+    assert_eq!(list.wallets.len(), 200);
+}
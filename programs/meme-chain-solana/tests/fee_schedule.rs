@@ -0,0 +1,33 @@
+use meme_chain_solana::curve::{effective_fee_bps, FeeSchedule};
+use proptest::prelude::*;
+
+const SCHEDULE: FeeSchedule = FeeSchedule {
+    start_bps: 2_000,
+    end_bps: 100,
+    decay_slots: 1_500, // ~10 minutes
+};
+
+#[test]
+fn decays_linearly_from_launch_fee_to_normal_fee() {
+    assert_eq!(effective_fee_bps(&SCHEDULE, 0), 2_000);
+    assert_eq!(effective_fee_bps(&SCHEDULE, 750), 1_050);
+    assert_eq!(effective_fee_bps(&SCHEDULE, 1_499), 102);
+    assert_eq!(effective_fee_bps(&SCHEDULE, 1_500), 100);
+    assert_eq!(effective_fee_bps(&SCHEDULE, u64::MAX), 100);
+}
+
+#[test]
+fn flat_schedule_is_just_the_normal_fee() {
+    let flat = FeeSchedule { start_bps: 100, end_bps: 100, decay_slots: 0 };
+    assert_eq!(effective_fee_bps(&flat, 0), 100);
+}
+
+proptest! {
+    #[test]
+    fn fee_never_rises_and_stays_within_schedule(a in 0u64..3_000, b in 0u64..3_000) {
+        let (early, late) = (a.min(b), a.max(b));
+        let early_fee = effective_fee_bps(&SCHEDULE, early);
+        prop_assert!(early_fee >= effective_fee_bps(&SCHEDULE, late));
+        prop_assert!((SCHEDULE.end_bps..=SCHEDULE.start_bps).contains(&early_fee));
+    }
+}
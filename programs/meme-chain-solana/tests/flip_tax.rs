@@ -0,0 +1,25 @@
+use meme_chain_solana::curve::flip_tax;
+
+const SOL: u64 = 1_000_000_000;
+const LAUNCH: i64 = 1_700_000_000;
+
+#[test]
+fn taxes_sells_inside_the_window() {
+    // 10% within 5 minutes of the wallet's first buy
+    assert_eq!(flip_tax(SOL, 1_000, 300, LAUNCH + 60, LAUNCH, LAUNCH + 65).unwrap(), SOL / 10);
+    assert_eq!(flip_tax(SOL, 1_000, 300, LAUNCH + 60, LAUNCH, LAUNCH + 359).unwrap(), SOL / 10);
+}
+
+#[test]
+fn no_tax_after_window_or_when_disabled() {
+    assert_eq!(flip_tax(SOL, 1_000, 300, LAUNCH + 60, LAUNCH, LAUNCH + 360).unwrap(), 0);
+    // Policy disabled
+    assert_eq!(flip_tax(SOL, 0, 300, LAUNCH + 60, LAUNCH, LAUNCH + 65).unwrap(), 0);
+}
+
+#[test]
+fn transferred_tokens_are_taxed_from_creation() {
+    // The wallet never bought from the curve: its tokens came by transfer
+    assert_eq!(flip_tax(SOL, 1_000, 300, 0, LAUNCH, LAUNCH + 65).unwrap(), SOL / 10);
+    assert_eq!(flip_tax(SOL, 1_000, 300, 0, LAUNCH, LAUNCH + 300).unwrap(), 0);
+}
@@ -0,0 +1,684 @@
+// ============================================================================
+// Program Test Harness
+// ============================================================================
+//
+// Runs the program in-process on a solana-program-test bank, next to the SPL
+// token and associated token programs. The program's entrypoint runs
+// natively, so plain `cargo test` covers the full instruction path without
+// an SBF toolchain. With SBF_OUT_DIR pointing at a compiled
+// meme_chain_solana.so, which needs the cdylib crate-type, that runs instead.
+//
+// Every transaction is paid for by the bank's payer, so a wallet's lamports
+// only move by what the program itself charges it.
+//
+// Shared by several test crates, none of which uses every helper.
+
+#![allow(dead_code)]
+
+use anchor_lang::prelude::{AccountInfo, Clock, Pubkey};
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, AccountDeserialize, AccountSerialize, AnchorDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::token::{self, spl_token};
+use meme_chain_solana::curve::{CurveType, FeeSchedule};
+use meme_chain_solana::{
+    accounts, instruction, AllowlistEntry, CreatorVestingParams, ErrorCode, InitialReserves, LaunchPolicy,
+    TradeQuote, TreasuryBucket,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::AccountSharedData;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+pub const SOL: u64 = 1_000_000_000;
+pub const TOKENS: u64 = 1_000_000; // One whole token at 6 decimals
+pub const TOTAL_SUPPLY: u64 = 1_000_000_000 * TOKENS;
+
+// Anchor wants the accounts to live as long as their AccountInfos, which the
+// runtime's native entrypoint signature can't promise
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(accounts.to_vec().into_boxed_slice());
+    meme_chain_solana::entry(program_id, accounts, data)
+}
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &meme_chain_solana::ID).0
+}
+
+pub struct Harness {
+    pub context: ProgramTestContext,
+}
+
+impl Harness {
+    pub async fn start() -> Self {
+        let program = ProgramTest::new(
+            "meme_chain_solana",
+            meme_chain_solana::ID,
+            processor!(process_instruction),
+        );
+        Self { context: program.start_with_context().await }
+    }
+
+    pub async fn send(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<(), BanksClientError> {
+        let payer = &self.context.payer;
+        let mut all_signers = vec![payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            self.context.last_blockhash,
+        );
+        self.context.banks_client.process_transaction(transaction).await
+    }
+
+    /// A new wallet holding `lamports`.
+    pub async fn wallet(&mut self, lamports: u64) -> Keypair {
+        let wallet = Keypair::new();
+        self.context.set_account(&wallet.pubkey(), &AccountSharedData::new(lamports, 0, &system_program::ID));
+        wallet
+    }
+
+    pub async fn clock(&mut self) -> Clock {
+        self.context.banks_client.get_sysvar().await.unwrap()
+    }
+
+    /// Moves the clock `seconds` ahead, along with the ~2.5 slots per second
+    /// the cluster would produce, and at least one slot so the next
+    /// transactions get a fresh blockhash.
+    pub async fn warp_seconds(&mut self, seconds: i64) {
+        let slots = (seconds.max(0) as u64 * 5).div_ceil(2).max(1);
+        self.warp(slots, seconds).await;
+    }
+
+    /// Moves `slots` slots ahead without moving the clock.
+    pub async fn warp_slots(&mut self, slots: u64) {
+        self.warp(slots, 0).await;
+    }
+
+    // Steps one slot at a time: longer warps make the bank hash every
+    // account to disk and run out of file handles
+    async fn warp(&mut self, slots: u64, seconds: i64) {
+        let clock = self.clock().await;
+        for slot in clock.slot + 1..=clock.slot + slots {
+            self.context.warp_to_slot(slot).unwrap();
+        }
+        self.context.set_sysvar(&Clock {
+            slot: clock.slot + slots,
+            unix_timestamp: clock.unix_timestamp + seconds,
+            ..clock
+        });
+    }
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let account = self.context.banks_client.get_account(address).await.unwrap().unwrap();
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn lamports(&mut self, address: Pubkey) -> u64 {
+        self.context.banks_client.get_balance(address).await.unwrap()
+    }
+
+    pub async fn exists(&mut self, address: Pubkey) -> bool {
+        self.context.banks_client.get_account(address).await.unwrap().is_some()
+    }
+
+    /// Edits a program account in place, for states the instructions
+    /// themselves can't get into.
+    pub async fn edit_account<T: AccountSerialize + AccountDeserialize>(&mut self, address: Pubkey, edit: impl FnOnce(&mut T)) {
+        let mut account = self.context.banks_client.get_account(address).await.unwrap().unwrap();
+        let mut state = T::try_deserialize(&mut account.data.as_slice()).unwrap();
+        edit(&mut state);
+        let mut data = Vec::new();
+        state.try_serialize(&mut data).unwrap();
+        account.data[..data.len()].copy_from_slice(&data);
+        self.context.set_account(&address, &account.into());
+    }
+
+    /// Overwrites an account's lamports.
+    pub async fn set_lamports(&mut self, address: Pubkey, lamports: u64) {
+        let mut account = self.context.banks_client.get_account(address).await.unwrap().unwrap();
+        account.lamports = lamports;
+        self.context.set_account(&address, &account.into());
+    }
+
+    pub async fn token_balance(&mut self, wallet: &Pubkey, meme: &Pubkey) -> u64 {
+        let address = get_associated_token_address(wallet, &pda(&[b"mint", meme.as_ref()]));
+        match self.context.banks_client.get_account(address).await.unwrap() {
+            Some(account) => token::TokenAccount::try_deserialize(&mut account.data.as_slice())
+                .unwrap()
+                .amount,
+            None => 0,
+        }
+    }
+
+    pub async fn initialize_protocol(&mut self, authority: &Keypair, protocol_fee_bps: u16) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: accounts::InitializeProtocol {
+                protocol: pda(&[b"protocol"]),
+                treasury: pda(&[b"treasury"]),
+                authority: authority.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::InitializeProtocol {
+                protocol_fee_bps,
+                creation_fee_lamports: 0,
+                graduation_threshold: 85 * SOL,
+                max_initial_buy_lamports: SOL,
+            }
+            .data(),
+        };
+        self.send(&[ix], &[authority]).await
+    }
+
+    pub async fn configure_fee_schedule(&mut self, authority: &Keypair, schedule: FeeSchedule) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: accounts::UpdateProtocol {
+                protocol: pda(&[b"protocol"]),
+                authority: authority.pubkey(),
+            }
+            .to_account_metas(None),
+            data: instruction::ConfigureFeeSchedule { schedule }.data(),
+        };
+        self.send(&[ix], &[authority]).await
+    }
+
+    pub async fn configure_attester(&mut self, authority: &Keypair, attester: Pubkey) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: accounts::UpdateProtocol {
+                protocol: pda(&[b"protocol"]),
+                authority: authority.pubkey(),
+            }
+            .to_account_metas(None),
+            data: instruction::ConfigureAttester { attester }.data(),
+        };
+        self.send(&[ix], &[authority]).await
+    }
+
+    pub async fn configure_referral_share(&mut self, authority: &Keypair, referral_share_bps: u16) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: accounts::UpdateProtocol {
+                protocol: pda(&[b"protocol"]),
+                authority: authority.pubkey(),
+            }
+            .to_account_metas(None),
+            data: instruction::ConfigureReferralShare { referral_share_bps }.data(),
+        };
+        self.send(&[ix], &[authority]).await
+    }
+
+    pub async fn withdraw_protocol_fees(
+        &mut self,
+        authority: &Keypair,
+        recipient: Pubkey,
+        amount: u64,
+        bucket: TreasuryBucket,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: accounts::WithdrawProtocolFees {
+                protocol: pda(&[b"protocol"]),
+                treasury: pda(&[b"treasury"]),
+                recipient,
+                authority: authority.pubkey(),
+            }
+            .to_account_metas(None),
+            data: instruction::WithdrawProtocolFees { amount, bucket }.data(),
+        };
+        self.send(&[ix], &[authority]).await
+    }
+
+    pub async fn issue_attestation(&mut self, attester: &Keypair, wallet: Pubkey, expires_at: i64) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: accounts::IssueAttestation {
+                protocol: pda(&[b"protocol"]),
+                wallet,
+                attestation: pda(&[b"attestation", wallet.as_ref()]),
+                attester: attester.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::IssueAttestation { expires_at }.data(),
+        };
+        self.send(&[ix], &[attester]).await
+    }
+
+    /// Launches the next meme and returns its address.
+    pub async fn create_meme(&mut self, creator: &Keypair, launch: Launch) -> Result<Pubkey, BanksClientError> {
+        let protocol: meme_chain_solana::Protocol = self.account(pda(&[b"protocol"])).await;
+        let meme = pda(&[b"meme", &protocol.total_memes_created.to_le_bytes()]);
+        let mint = pda(&[b"mint", meme.as_ref()]);
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: accounts::CreateMemeToken {
+                protocol: pda(&[b"protocol"]),
+                meme,
+                image_registry: pda(&[b"image", &launch.image_hash]),
+                mint,
+                creator_token_account: get_associated_token_address(&creator.pubkey(), &mint),
+                token_vault: pda(&[b"token_vault", meme.as_ref()]),
+                creator_vesting: launch.vesting.map(|_| pda(&[b"vesting", meme.as_ref()])),
+                vesting_vault: launch.vesting.map(|_| pda(&[b"vesting_vault", meme.as_ref()])),
+                creator_wallet_state: launch
+                    .initial_buy_sol
+                    .map(|_| pda(&[b"wallet", meme.as_ref(), creator.pubkey().as_ref()])),
+                bonding_curve_vault: pda(&[b"vault", meme.as_ref()]),
+                holder_stats: pda(&[b"holders", meme.as_ref()]),
+                reward_pool: pda(&[b"reward_pool", meme.as_ref()]),
+                price_oracle: pda(&[b"oracle", meme.as_ref()]),
+                launch_batch: (launch.policy.commit_window_slots > 0 || launch.policy.auction_window_slots > 0)
+                    .then(|| pda(&[b"batch", meme.as_ref()])),
+                denylist: launch.policy.denylist_enabled.then(|| pda(&[b"denylist", meme.as_ref()])),
+                creator: creator.pubkey(),
+                treasury: pda(&[b"treasury"]),
+                token_program: spl_token::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::CreateMemeToken {
+                name: launch.name,
+                symbol: launch.symbol,
+                uri: "https://example.com/meme.json".to_string(),
+                image_hash: launch.image_hash,
+                perceptual_hash: 0,
+                initial_reserves: launch.reserves,
+                curve_type: launch.curve_type,
+                creator_vesting: launch.vesting,
+                initial_buy_sol: launch.initial_buy_sol,
+                launch_policy: launch.policy,
+            }
+            .data(),
+        };
+        self.send(&[ix], &[creator]).await.map(|()| meme)
+    }
+
+    pub async fn buy(&mut self, buyer: &Keypair, meme: Pubkey, sol_amount: u64, min_tokens_out: u64, max_slippage_bps: u16) -> Result<(), BanksClientError> {
+        self.buy_with(buyer, meme, sol_amount, min_tokens_out, max_slippage_bps, None, None).await
+    }
+
+    /// A buy sent through `referrer`, if any, with an allowlist entry. The
+    /// meme's denylist and the buyer's attestation are passed when they exist.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn buy_with(
+        &mut self,
+        buyer: &Keypair,
+        meme: Pubkey,
+        sol_amount: u64,
+        min_tokens_out: u64,
+        max_slippage_bps: u16,
+        referrer: Option<Pubkey>,
+        allowlist: Option<AllowlistEntry>,
+    ) -> Result<(), BanksClientError> {
+        let state: meme_chain_solana::MemeToken = self.account(meme).await;
+        let mint = pda(&[b"mint", meme.as_ref()]);
+        let attestation = self.attestation(buyer.pubkey()).await;
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: accounts::BuyTokens {
+                protocol: pda(&[b"protocol"]),
+                meme,
+                mint,
+                token_vault: pda(&[b"token_vault", meme.as_ref()]),
+                buyer_token_account: get_associated_token_address(&buyer.pubkey(), &mint),
+                bonding_curve_vault: pda(&[b"vault", meme.as_ref()]),
+                wallet_state: pda(&[b"wallet", meme.as_ref(), buyer.pubkey().as_ref()]),
+                holder_stats: pda(&[b"holders", meme.as_ref()]),
+                price_oracle: pda(&[b"oracle", meme.as_ref()]),
+                denylist: state.launch_policy.denylist_enabled.then(|| pda(&[b"denylist", meme.as_ref()])),
+                attestation,
+                referrer_state: referrer.map(|referrer| pda(&[b"referrer", referrer.as_ref()])),
+                buyer: buyer.pubkey(),
+                creator: state.creator,
+                treasury: pda(&[b"treasury"]),
+                token_program: spl_token::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::BuyTokens {
+                sol_amount,
+                min_tokens_out,
+                max_slippage_bps,
+                allowlist,
+            }
+            .data(),
+        };
+        self.send(&[ix], &[buyer]).await
+    }
+
+    pub async fn sell(&mut self, seller: &Keypair, meme: Pubkey, token_amount: u64, min_sol_out: u64, max_slippage_bps: u16) -> Result<(), BanksClientError> {
+        let state: meme_chain_solana::MemeToken = self.account(meme).await;
+        let mint = pda(&[b"mint", meme.as_ref()]);
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: accounts::SellTokens {
+                protocol: pda(&[b"protocol"]),
+                meme,
+                mint,
+                token_vault: pda(&[b"token_vault", meme.as_ref()]),
+                seller_token_account: get_associated_token_address(&seller.pubkey(), &mint),
+                bonding_curve_vault: pda(&[b"vault", meme.as_ref()]),
+                wallet_state: pda(&[b"wallet", meme.as_ref(), seller.pubkey().as_ref()]),
+                holder_stats: pda(&[b"holders", meme.as_ref()]),
+                price_oracle: pda(&[b"oracle", meme.as_ref()]),
+                reward_pool: pda(&[b"reward_pool", meme.as_ref()]),
+                incinerator: None,
+                referrer_state: None,
+                seller: seller.pubkey(),
+                creator: state.creator,
+                treasury: pda(&[b"treasury"]),
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::SellTokens {
+                token_amount,
+                min_sol_out,
+                max_slippage_bps,
+            }
+            .data(),
+        };
+        self.send(&[ix], &[seller]).await
+    }
+
+    /// What buying with `sol_amount` would currently get `trader`, from a
+    /// simulated quote_buy.
+    pub async fn quote_buy(&mut self, trader: Pubkey, meme: Pubkey, sol_amount: u64) -> TradeQuote {
+        self.quote(trader, meme, instruction::QuoteBuy { sol_amount, allowlist: None }.data()).await
+    }
+
+    /// What selling `token_amount` would currently pay `trader`, from a
+    /// simulated quote_sell.
+    pub async fn quote_sell(&mut self, trader: Pubkey, meme: Pubkey, token_amount: u64) -> TradeQuote {
+        self.quote(trader, meme, instruction::QuoteSell { token_amount }.data()).await
+    }
+
+    async fn quote(&mut self, trader: Pubkey, meme: Pubkey, data: Vec<u8>) -> TradeQuote {
+        let mint = pda(&[b"mint", meme.as_ref()]);
+        let trader_token_account = get_associated_token_address(&trader, &mint);
+        let wallet_state = pda(&[b"wallet", meme.as_ref(), trader.as_ref()]);
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: accounts::QuoteTrade {
+                protocol: pda(&[b"protocol"]),
+                meme,
+                trader,
+                trader_token_account: self.exists(trader_token_account).await.then_some(trader_token_account),
+                wallet_state: self.exists(wallet_state).await.then_some(wallet_state),
+                denylist: self.denylist(meme).await,
+                attestation: self.attestation(trader).await,
+                referrer_state: None,
+            }
+            .to_account_metas(None),
+            data,
+        };
+        let payer = &self.context.payer;
+        let transaction = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[payer], self.context.last_blockhash);
+        let simulation = self.context.banks_client.simulate_transaction(transaction).await.unwrap();
+        simulation.result.unwrap().unwrap();
+        let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+        TradeQuote::try_from_slice(&return_data.data).unwrap()
+    }
+
+    pub async fn register_referrer(&mut self, referrer: &Keypair) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: accounts::RegisterReferrer {
+                referrer_state: pda(&[b"referrer", referrer.pubkey().as_ref()]),
+                referrer: referrer.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::RegisterReferrer {}.data(),
+        };
+        self.send(&[ix], &[referrer]).await
+    }
+
+    pub async fn claim_referral_fees(&mut self, referrer: &Keypair) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: accounts::ClaimReferralFees {
+                referrer_state: pda(&[b"referrer", referrer.pubkey().as_ref()]),
+                referrer: referrer.pubkey(),
+            }
+            .to_account_metas(None),
+            data: instruction::ClaimReferralFees {}.data(),
+        };
+        self.send(&[ix], &[referrer]).await
+    }
+
+    pub async fn claim_vested(&mut self, creator: &Keypair, meme: Pubkey) -> Result<(), BanksClientError> {
+        let mint = pda(&[b"mint", meme.as_ref()]);
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: accounts::ClaimVested {
+                meme,
+                creator_vesting: pda(&[b"vesting", meme.as_ref()]),
+                vesting_vault: pda(&[b"vesting_vault", meme.as_ref()]),
+                mint,
+                creator_token_account: get_associated_token_address(&creator.pubkey(), &mint),
+                creator: creator.pubkey(),
+                token_program: spl_token::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::ClaimVested {}.data(),
+        };
+        self.send(&[ix], &[creator]).await
+    }
+
+    pub async fn update_denylist(&mut self, creator: &Keypair, meme: Pubkey, add: Vec<Pubkey>, remove: Vec<Pubkey>) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: accounts::UpdateDenylist {
+                meme,
+                denylist: pda(&[b"denylist", meme.as_ref()]),
+                creator: creator.pubkey(),
+            }
+            .to_account_metas(None),
+            data: instruction::UpdateDenylist { add, remove }.data(),
+        };
+        self.send(&[ix], &[creator]).await
+    }
+
+    pub async fn commit_buy(&mut self, buyer: &Keypair, meme: Pubkey, commitment: [u8; 32], escrow_lamports: u64) -> Result<(), BanksClientError> {
+        self.commit_buy_with(buyer, meme, commitment, escrow_lamports, None).await
+    }
+
+    pub async fn commit_buy_with(
+        &mut self,
+        buyer: &Keypair,
+        meme: Pubkey,
+        commitment: [u8; 32],
+        escrow_lamports: u64,
+        allowlist: Option<AllowlistEntry>,
+    ) -> Result<(), BanksClientError> {
+        let denylist = self.denylist(meme).await;
+        let attestation = self.attestation(buyer.pubkey()).await;
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: accounts::CommitBuy {
+                protocol: pda(&[b"protocol"]),
+                meme,
+                launch_batch: pda(&[b"batch", meme.as_ref()]),
+                batch_order: pda(&[b"order", meme.as_ref(), buyer.pubkey().as_ref()]),
+                denylist,
+                attestation,
+                buyer: buyer.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::CommitBuy { commitment, escrow_lamports, allowlist }.data(),
+        };
+        self.send(&[ix], &[buyer]).await
+    }
+
+    pub async fn reveal_buy(&mut self, buyer: &Keypair, meme: Pubkey, amount: u64, salt: [u8; 32]) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: accounts::RevealBuy {
+                protocol: pda(&[b"protocol"]),
+                meme,
+                launch_batch: pda(&[b"batch", meme.as_ref()]),
+                batch_order: pda(&[b"order", meme.as_ref(), buyer.pubkey().as_ref()]),
+                buyer: buyer.pubkey(),
+            }
+            .to_account_metas(None),
+            data: instruction::RevealBuy { amount, salt }.data(),
+        };
+        self.send(&[ix], &[buyer]).await
+    }
+
+    pub async fn settle_launch_batch(&mut self, meme: Pubkey) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: settle_accounts(meme),
+            data: instruction::SettleLaunchBatch {}.data(),
+        };
+        self.send(&[ix], &[]).await
+    }
+
+    pub async fn place_auction_bid(&mut self, bidder: &Keypair, meme: Pubkey, amount: u64) -> Result<(), BanksClientError> {
+        self.place_auction_bid_with(bidder, meme, amount, None).await
+    }
+
+    pub async fn place_auction_bid_with(
+        &mut self,
+        bidder: &Keypair,
+        meme: Pubkey,
+        amount: u64,
+        allowlist: Option<AllowlistEntry>,
+    ) -> Result<(), BanksClientError> {
+        let denylist = self.denylist(meme).await;
+        let attestation = self.attestation(bidder.pubkey()).await;
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: accounts::PlaceAuctionBid {
+                protocol: pda(&[b"protocol"]),
+                meme,
+                launch_batch: pda(&[b"batch", meme.as_ref()]),
+                batch_order: pda(&[b"order", meme.as_ref(), bidder.pubkey().as_ref()]),
+                denylist,
+                attestation,
+                bidder: bidder.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::PlaceAuctionBid { amount, allowlist }.data(),
+        };
+        self.send(&[ix], &[bidder]).await
+    }
+
+    pub async fn settle_launch_auction(&mut self, meme: Pubkey) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: settle_accounts(meme),
+            data: instruction::SettleLaunchAuction {}.data(),
+        };
+        self.send(&[ix], &[]).await
+    }
+
+    pub async fn claim_batch_order(&mut self, buyer: &Keypair, meme: Pubkey) -> Result<(), BanksClientError> {
+        let state: meme_chain_solana::MemeToken = self.account(meme).await;
+        let mint = pda(&[b"mint", meme.as_ref()]);
+        let ix = Instruction {
+            program_id: meme_chain_solana::ID,
+            accounts: accounts::ClaimBatchOrder {
+                protocol: pda(&[b"protocol"]),
+                meme,
+                mint,
+                token_vault: pda(&[b"token_vault", meme.as_ref()]),
+                buyer_token_account: get_associated_token_address(&buyer.pubkey(), &mint),
+                launch_batch: pda(&[b"batch", meme.as_ref()]),
+                bonding_curve_vault: pda(&[b"vault", meme.as_ref()]),
+                batch_order: pda(&[b"order", meme.as_ref(), buyer.pubkey().as_ref()]),
+                wallet_state: pda(&[b"wallet", meme.as_ref(), buyer.pubkey().as_ref()]),
+                holder_stats: pda(&[b"holders", meme.as_ref()]),
+                treasury: pda(&[b"treasury"]),
+                denylist: state.launch_policy.denylist_enabled.then(|| pda(&[b"denylist", meme.as_ref()])),
+                creator: state.creator,
+                buyer: buyer.pubkey(),
+                token_program: spl_token::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::ClaimBatchOrder {}.data(),
+        };
+        self.send(&[ix], &[buyer]).await
+    }
+
+    // The meme's denylist, if it has one
+    async fn denylist(&mut self, meme: Pubkey) -> Option<Pubkey> {
+        let state: meme_chain_solana::MemeToken = self.account(meme).await;
+        state.launch_policy.denylist_enabled.then(|| pda(&[b"denylist", meme.as_ref()]))
+    }
+
+    // The wallet's attestation, if it has one
+    async fn attestation(&mut self, wallet: Pubkey) -> Option<Pubkey> {
+        let attestation = pda(&[b"attestation", wallet.as_ref()]);
+        self.exists(attestation).await.then_some(attestation)
+    }
+}
+
+// Both launch batch settlements take the same accounts
+fn settle_accounts(meme: Pubkey) -> Vec<anchor_lang::prelude::AccountMeta> {
+    accounts::SettleLaunchBatch {
+        protocol: pda(&[b"protocol"]),
+        meme,
+        launch_batch: pda(&[b"batch", meme.as_ref()]),
+        bonding_curve_vault: pda(&[b"vault", meme.as_ref()]),
+        price_oracle: pda(&[b"oracle", meme.as_ref()]),
+    }
+    .to_account_metas(None)
+}
+
+// create_meme arguments the tests vary
+pub struct Launch {
+    pub name: String,
+    pub symbol: String,
+    pub image_hash: [u8; 32],
+    pub initial_buy_sol: Option<u64>,
+    pub policy: LaunchPolicy,
+    pub reserves: InitialReserves,
+    pub curve_type: CurveType,
+    pub vesting: Option<CreatorVestingParams>,
+}
+
+impl Launch {
+    /// A valid launch whose image is unique to `seed`.
+    pub fn new(seed: u8) -> Self {
+        Self {
+            name: format!("Meme {seed}"),
+            symbol: format!("MEME{seed}"),
+            image_hash: [seed; 32],
+            initial_buy_sol: None,
+            policy: LaunchPolicy::default(),
+            reserves: InitialReserves::Preset(0),
+            curve_type: CurveType::ConstantProduct,
+            vesting: None,
+        }
+    }
+}
+
+/// Asserts the transaction failed with the program's `error`.
+pub fn assert_rejected(result: Result<(), BanksClientError>, error: ErrorCode) {
+    let code = match result {
+        Err(err) => match err.unwrap() {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => Some(code),
+            _ => None,
+        },
+        Ok(()) => None,
+    };
+    assert_eq!(code, Some(u32::from(error)), "expected {error}");
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::Pubkey;
+use meme_chain_solana::holders::{concentration_bps, record_balance, TopHolder, TOP_HOLDERS};
+
+const TOTAL_SUPPLY: u64 = 1_000_000_000_000_000;
+
+fn wallet(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+fn board() -> [TopHolder; TOP_HOLDERS] {
+    [TopHolder::default(); TOP_HOLDERS]
+}
+
+#[test]
+fn keeps_board_sorted_and_updates_in_place() {
+    let mut top = board();
+    record_balance(&mut top, wallet(1), 100);
+    record_balance(&mut top, wallet(2), 300);
+    record_balance(&mut top, wallet(3), 200);
+    record_balance(&mut top, wallet(1), 400);
+
+    let order: Vec<_> = top.iter().take(3).map(|h| (h.wallet, h.balance)).collect();
+    assert_eq!(order, vec![(wallet(1), 400), (wallet(2), 300), (wallet(3), 200)]);
+    assert_eq!(top[3], TopHolder::default());
+}
+
+#[test]
+fn zero_balance_drops_wallet() {
+    let mut top = board();
+    record_balance(&mut top, wallet(1), 100);
+    record_balance(&mut top, wallet(2), 50);
+    record_balance(&mut top, wallet(1), 0);
+
+    assert_eq!(top[0], TopHolder { wallet: wallet(2), balance: 50 });
+    assert_eq!(top[1], TopHolder::default());
+}
+
+#[test]
+fn smaller_wallets_fall_off_a_full_board() {
+    let mut top = board();
+    for n in 1..=TOP_HOLDERS as u8 {
+        record_balance(&mut top, wallet(n), n as u64 * 10);
+    }
+    // Ties don't displace wallets already on the board
+    record_balance(&mut top, wallet(100), 10);
+    assert!(top.iter().all(|h| h.wallet != wallet(100)));
+
+    record_balance(&mut top, wallet(101), 15);
+    assert!(top.iter().any(|h| h.wallet == wallet(101)));
+    assert!(top.iter().all(|h| h.wallet != wallet(1)));
+}
+
+#[test]
+fn concentration_is_share_of_total_supply() {
+    let mut top = board();
+    record_balance(&mut top, wallet(1), TOTAL_SUPPLY / 50); // 2%
+    record_balance(&mut top, wallet(2), TOTAL_SUPPLY / 100); // 1%
+
+    assert_eq!(concentration_bps(&top, 1, TOTAL_SUPPLY), 200);
+    assert_eq!(concentration_bps(&top, TOP_HOLDERS, TOTAL_SUPPLY), 300);
+    assert_eq!(concentration_bps(&top, TOP_HOLDERS, 0), 0);
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::Pubkey;
+use meme_chain_solana::batch::{cap_fill, clear_auction, commitment, fill_order, OrderFill};
+use meme_chain_solana::curve;
+use proptest::prelude::*;
+
+const SOL: u64 = 1_000_000_000;
+const VIRTUAL_SOL: u64 = 30 * SOL;
+const VIRTUAL_TOKEN: u64 = 1_073_000_000_000_000;
+const TRANCHE: u64 = 80_000_000_000_000; // 10% of the curve supply
+
+#[test]
+fn commitment_binds_buyer_amount_and_salt() {
+    let buyer = Pubkey::new_from_array([1; 32]);
+    let salt = [7; 32];
+    let committed = commitment(&buyer, 2 * SOL, &salt);
+
+    assert_eq!(committed, commitment(&buyer, 2 * SOL, &salt));
+    assert_ne!(committed, commitment(&buyer, 3 * SOL, &salt));
+    assert_ne!(committed, commitment(&buyer, 2 * SOL, &[8; 32]));
+    assert_ne!(committed, commitment(&Pubkey::new_from_array([2; 32]), 2 * SOL, &salt));
+}
+
+#[test]
+fn orders_share_one_price() {
+    // 10 SOL of orders bought 300M tokens
+    let small = fill_order(SOL, 10 * SOL, 10 * SOL, 300_000_000).unwrap();
+    let large = fill_order(4 * SOL, 10 * SOL, 10 * SOL, 300_000_000).unwrap();
+    assert_eq!(small, OrderFill { tokens: 30_000_000, cost: SOL, refund: 0 });
+    assert_eq!(large, OrderFill { tokens: 120_000_000, cost: 4 * SOL, refund: 0 });
+
+    // The curve ran out after 8 of the 10 SOL: a fifth of every order comes back
+    let clamped = fill_order(4 * SOL, 10 * SOL, 8 * SOL, 300_000_000).unwrap();
+    assert_eq!(clamped.cost, 4 * SOL * 4 / 5);
+    assert_eq!(clamped.refund, 4 * SOL / 5);
+}
+
+#[test]
+fn fills_cannot_exceed_the_batch() {
+    assert!(fill_order(11 * SOL, 10 * SOL, 10 * SOL, 1).is_err());
+    assert!(fill_order(SOL, 10 * SOL, 11 * SOL, 1).is_err());
+    assert_eq!(fill_order(0, 0, 0, 0).unwrap(), OrderFill { tokens: 0, cost: 0, refund: 0 });
+}
+
+#[test]
+fn capped_orders_return_the_excess_at_their_own_price() {
+    let fill = fill_order(4 * SOL, 10 * SOL, 8 * SOL, 300_000_000).unwrap();
+    assert_eq!(cap_fill(fill, fill.tokens), (fill, OrderFill { tokens: 0, cost: 0, refund: 0 }));
+
+    let (capped, excess) = cap_fill(fill, 30_000_000);
+    assert_eq!(capped.tokens, 30_000_000);
+    assert_eq!(excess.tokens, fill.tokens - 30_000_000);
+    assert_eq!(capped.cost + excess.cost, fill.cost);
+    assert_eq!(capped.refund, fill.refund);
+    assert_eq!(excess.cost, fill.cost / 4 * 3);
+}
+
+#[test]
+fn oversubscribed_auction_clears_the_tranche_at_one_price() {
+    // The curve would charge ~2.4 SOL for the tranche; bidders offered 10
+    let fill = clear_auction(VIRTUAL_SOL, VIRTUAL_TOKEN, 10 * SOL, TRANCHE).unwrap();
+    assert_eq!((fill.sol_in, fill.tokens_out), (10 * SOL, TRANCHE));
+    assert_eq!(fill.new_virtual_token, VIRTUAL_TOKEN - TRANCHE);
+
+    // The curve carries on from the clearing price
+    let clearing = 10 * SOL as u128 * 1_000_000 / TRANCHE as u128;
+    let spot = fill.new_virtual_sol as u128 * 1_000_000 / fill.new_virtual_token as u128;
+    assert_eq!(spot, clearing);
+
+    // Selling the whole tranche back can't pay out more than the bids put in
+    let sell = curve::quote_sell(fill.new_virtual_sol, fill.new_virtual_token, TRANCHE).unwrap();
+    assert!(sell.sol_out < 10 * SOL);
+}
+
+#[test]
+fn undersubscribed_auction_buys_on_the_curve() {
+    let fill = clear_auction(VIRTUAL_SOL, VIRTUAL_TOKEN, SOL, TRANCHE).unwrap();
+    assert_eq!(fill, curve::quote_buy(VIRTUAL_SOL, VIRTUAL_TOKEN, SOL, TRANCHE).unwrap());
+    assert!(fill.tokens_out < TRANCHE);
+    assert!(clear_auction(VIRTUAL_SOL, VIRTUAL_TOKEN, 0, TRANCHE).is_err());
+}
+
+proptest! {
+    #[test]
+    fn claims_never_add_up_to_more_than_the_fill(
+        orders in prop::collection::vec(1u64..100 * SOL, 1..50),
+        filled_bps in 1u64..=10_000,
+        tokens_filled in 1u64..800_000_000_000_000,
+    ) {
+        let total: u64 = orders.iter().sum();
+        let sol_filled = total / 10_000 * filled_bps;
+        let fills: Vec<_> = orders
+            .iter()
+            .map(|&amount| fill_order(amount, total, sol_filled, tokens_filled).unwrap())
+            .collect();
+
+        let tokens: u64 = fills.iter().map(|f| f.tokens).sum();
+        let cost: u64 = fills.iter().map(|f| f.cost).sum();
+        let refund: u64 = fills.iter().map(|f| f.refund).sum();
+        prop_assert!(tokens <= tokens_filled);
+        prop_assert!(cost >= sol_filled);
+        // The batch escrow holds total - sol_filled after settlement
+        prop_assert!(refund <= total - sol_filled);
+    }
+
+    #[test]
+    fn capping_never_raises_the_price(
+        amount in 1u64..100 * SOL,
+        tokens_filled in 1u64..800_000_000_000_000,
+        max_tokens in 0u64..800_000_000_000_000,
+    ) {
+        let fill = fill_order(amount, amount, amount, tokens_filled).unwrap();
+        let (capped, excess) = cap_fill(fill, max_tokens);
+        prop_assert_eq!(capped.tokens + excess.tokens, fill.tokens);
+        prop_assert_eq!(capped.cost + excess.cost, fill.cost);
+        prop_assert!(capped.tokens <= max_tokens);
+        // The excess goes back no dearer than the order paid
+        prop_assert!(excess.cost as u128 * fill.tokens as u128 <= fill.cost as u128 * excess.tokens as u128);
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::Pubkey;
+use meme_chain_solana::oracle::{Observation, OBSERVATIONS, OBSERVATION_INTERVAL_SECONDS};
+use meme_chain_solana::PriceOracle;
+
+const LAUNCH: i64 = 1_700_000_000;
+
+fn oracle(launch_price: u64) -> PriceOracle {
+    let mut oracle = PriceOracle {
+        meme: Pubkey::default(),
+        price_cumulative: 0,
+        last_price: 0,
+        last_timestamp: LAUNCH,
+        observations: [Observation::default(); OBSERVATIONS],
+        observation_index: 0,
+        observation_count: 0,
+        bump: 255,
+    };
+    oracle.record_price(launch_price, LAUNCH);
+    oracle
+}
+
+#[test]
+fn twap_weights_prices_by_time_held() {
+    let mut oracle = oracle(100);
+    oracle.record_price(200, LAUNCH + 600);
+    oracle.record_price(400, LAUNCH + 900);
+
+    // 100 for 600s, 200 for 300s, 400 for 300s
+    assert_eq!(oracle.twap(LAUNCH + 1_200, 1_200).unwrap(), (100 * 600 + 200 * 300 + 400 * 300) / 1_200);
+    // Only snapshots at least the window old count, so this averages from launch
+    assert_eq!(oracle.twap(LAUNCH + 1_200, 1_000).unwrap(), (100 * 600 + 200 * 300 + 400 * 300) / 1_200);
+    // From the snapshot taken at +600
+    assert_eq!(oracle.twap(LAUNCH + 1_200, 600).unwrap(), (200 * 300 + 400 * 300) / 600);
+}
+
+#[test]
+fn same_second_spikes_do_not_move_the_twap() {
+    let mut oracle = oracle(100);
+    oracle.record_price(100, LAUNCH + 600);
+    let before = oracle.twap(LAUNCH + 600, 600).unwrap();
+
+    // Pump and dump inside one transaction
+    oracle.record_price(1_000_000, LAUNCH + 600);
+    oracle.record_price(100, LAUNCH + 600);
+    assert_eq!(oracle.twap(LAUNCH + 600, 600).unwrap(), before);
+}
+
+#[test]
+fn window_must_be_covered_by_history() {
+    let oracle = oracle(100);
+    assert_eq!(oracle.twap(LAUNCH + 300, 300).unwrap(), 100);
+    assert!(oracle.twap(LAUNCH + 300, 301).is_err());
+    assert!(oracle.twap(LAUNCH + 300, 0).is_err());
+}
+
+#[test]
+fn bursts_of_trades_do_not_flush_the_ring() {
+    let mut oracle = oracle(100);
+    for second in 1..=OBSERVATIONS as i64 * 10 {
+        oracle.record_price(100 + second as u64, LAUNCH + second);
+    }
+    // One snapshot per interval: launch is still in the ring
+    assert_eq!(oracle.observation_count as i64, 1 + OBSERVATIONS as i64 * 10 / OBSERVATION_INTERVAL_SECONDS);
+    assert!(oracle.twap(LAUNCH + OBSERVATIONS as i64 * 10, OBSERVATIONS as u32 * 10).is_ok());
+}
+
+#[test]
+fn ring_keeps_the_newest_observations() {
+    let mut oracle = oracle(100);
+    let end = LAUNCH + 2 * OBSERVATIONS as i64 * OBSERVATION_INTERVAL_SECONDS;
+    let mut now = LAUNCH;
+    while now < end {
+        now += OBSERVATION_INTERVAL_SECONDS;
+        oracle.record_price(100, now);
+    }
+    assert_eq!(oracle.observation_count as usize, OBSERVATIONS);
+
+    let span = (OBSERVATIONS as i64 - 1) * OBSERVATION_INTERVAL_SECONDS;
+    assert_eq!(oracle.twap(end, span as u32).unwrap(), 100);
+    assert!(oracle.twap(end, span as u32 + 1).is_err());
+}
@@ -0,0 +1,743 @@
+mod harness;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::Mint;
+use harness::{assert_rejected, pda, Harness, Launch, SOL, TOKENS, TOTAL_SUPPLY};
+use meme_chain_solana::curve::{self, CurveType, FeeSchedule};
+use meme_chain_solana::{allowlist, batch};
+use meme_chain_solana::{
+    AllowlistEntry, CreatorVestingParams, ErrorCode, InitialReserves, LaunchBatch, LaunchPolicy, MemeToken, Protocol,
+    ReferrerState, TreasuryBucket, WalletState,
+};
+use solana_sdk::signature::{Keypair, Signer};
+
+const LAUNCH_COOLDOWN_SECONDS: i64 = 60;
+const LAUNCH_PERIOD_SECONDS: i64 = 900;
+
+async fn launched() -> (Harness, Keypair, solana_sdk::pubkey::Pubkey) {
+    let mut harness = Harness::start().await;
+    let authority = harness.wallet(10 * SOL).await;
+    harness.initialize_protocol(&authority, 100).await.unwrap();
+    let creator = harness.wallet(10 * SOL).await;
+    let meme = harness.create_meme(&creator, Launch::new(1)).await.unwrap();
+    (harness, authority, meme)
+}
+
+// Lamports that buy `tokens` off a constant product curve, rounded up
+fn sol_for_tokens(meme: &MemeToken, tokens: u64) -> u64 {
+    let (vs, vt) = (meme.virtual_sol_reserves as u128, meme.virtual_token_reserves as u128);
+    (vs * tokens as u128).div_ceil(vt - tokens as u128) as u64
+}
+
+#[tokio::test]
+async fn launch_buy_sell_and_graduate() {
+    let (mut harness, _, meme) = launched().await;
+    let vault = pda(&[b"vault", meme.as_ref()]);
+    let vault_rent = harness.lamports(vault).await;
+
+    harness.warp_seconds(LAUNCH_COOLDOWN_SECONDS).await;
+    let buyer = harness.wallet(10 * SOL).await;
+    let before: MemeToken = harness.account(meme).await;
+    let sol = SOL / 10;
+    let quote = curve::quote_buy(before.virtual_sol_reserves, before.virtual_token_reserves, sol, u64::MAX).unwrap();
+    harness.buy(&buyer, meme, sol, quote.tokens_out, 100).await.unwrap();
+
+    let state: MemeToken = harness.account(meme).await;
+    assert_eq!(harness.token_balance(&buyer.pubkey(), &meme).await, quote.tokens_out);
+    assert_eq!(state.circulating_supply, quote.tokens_out);
+    assert_eq!(state.real_sol_reserves, sol);
+    assert_eq!(state.holders_count, 1);
+    assert_eq!(harness.lamports(vault).await, vault_rent + sol);
+    let protocol: Protocol = harness.account(pda(&[b"protocol"])).await;
+    assert_eq!(protocol.total_protocol_fees, (sol / 100) as u128);
+
+    // Sell half back
+    harness.warp_seconds(1).await;
+    let lamports_before = harness.lamports(buyer.pubkey()).await;
+    let half = quote.tokens_out / 2;
+    let sell = curve::quote_sell_on(&state.curve_position(), half).unwrap();
+    harness.sell(&buyer, meme, half, sell.sol_out * 98 / 100, 100).await.unwrap();
+
+    let state: MemeToken = harness.account(meme).await;
+    let proceeds = harness.lamports(buyer.pubkey()).await - lamports_before;
+    assert_eq!(proceeds, sell.sol_out - sell.sol_out / 100);
+    assert_eq!(harness.token_balance(&buyer.pubkey(), &meme).await, quote.tokens_out - half);
+    assert_eq!(state.real_sol_reserves, sol - sell.sol_out);
+    assert_eq!(harness.lamports(vault).await, vault_rent + state.real_sol_reserves);
+
+    // Buy out the curve after the launch period, each wallet just under the
+    // 2% limit, until the last buy is clamped to what's left
+    harness.warp_seconds(LAUNCH_PERIOD_SECONDS).await;
+    let per_wallet = TOTAL_SUPPLY / 50 - TOKENS;
+    let mut buys = 0;
+    loop {
+        let state: MemeToken = harness.account(meme).await;
+        if state.is_graduated {
+            break;
+        }
+        let whale = harness.wallet(20 * SOL).await;
+        harness.buy(&whale, meme, sol_for_tokens(&state, per_wallet), 0, 100).await.unwrap();
+        harness.warp_seconds(1).await;
+        buys += 1;
+        assert!(buys <= 50, "curve never sold out");
+    }
+
+    let state: MemeToken = harness.account(meme).await;
+    assert_eq!(state.circulating_supply, state.bonding_curve_supply);
+    assert_eq!(harness.lamports(vault).await, vault_rent + state.real_sol_reserves);
+    let protocol: Protocol = harness.account(pda(&[b"protocol"])).await;
+    assert_eq!(protocol.total_graduations, 1);
+
+    // The curve is closed both ways
+    assert_rejected(harness.buy(&buyer, meme, SOL, 0, 100).await, ErrorCode::AlreadyGraduated);
+    assert_rejected(harness.sell(&buyer, meme, half / 2, 0, 100).await, ErrorCode::AlreadyGraduated);
+}
+
+#[tokio::test]
+async fn launch_and_trade_cooldowns() {
+    let (mut harness, _, meme) = launched().await;
+    let first = harness.wallet(10 * SOL).await;
+    let second = harness.wallet(10 * SOL).await;
+
+    // Creation counts as a trade for the one-second cooldown
+    assert_rejected(harness.buy(&first, meme, SOL / 10, 0, 100).await, ErrorCode::TradeTooFast);
+    harness.warp_seconds(1).await;
+    assert_rejected(harness.buy(&first, meme, SOL / 10, 0, 100).await, ErrorCode::LaunchCooldownActive);
+    harness.warp_seconds(LAUNCH_COOLDOWN_SECONDS - 2).await;
+    assert_rejected(harness.buy(&first, meme, SOL / 10, 0, 100).await, ErrorCode::LaunchCooldownActive);
+
+    harness.warp_seconds(1).await;
+    harness.buy(&first, meme, SOL / 10, 0, 100).await.unwrap();
+    assert_rejected(harness.buy(&second, meme, SOL / 10, 0, 100).await, ErrorCode::TradeTooFast);
+    assert_rejected(harness.sell(&first, meme, TOKENS, 0, 100).await, ErrorCode::TradeTooFast);
+
+    harness.warp_seconds(1).await;
+    harness.buy(&second, meme, SOL / 10, 0, 100).await.unwrap();
+}
+
+#[tokio::test]
+async fn wallet_limit_tightens_during_launch() {
+    let (mut harness, _, meme) = launched().await;
+    let buyer = harness.wallet(10 * SOL).await;
+    harness.warp_seconds(LAUNCH_COOLDOWN_SECONDS).await;
+
+    // 0.5% of supply during the launch period
+    let state: MemeToken = harness.account(meme).await;
+    let launch_limit = TOTAL_SUPPLY / 200;
+    assert_rejected(
+        harness.buy(&buyer, meme, sol_for_tokens(&state, launch_limit + TOKENS), 0, 100).await,
+        ErrorCode::MaxWalletExceeded,
+    );
+    harness.buy(&buyer, meme, sol_for_tokens(&state, launch_limit - TOKENS), 0, 100).await.unwrap();
+
+    // 2% afterwards
+    harness.warp_seconds(LAUNCH_PERIOD_SECONDS).await;
+    let state: MemeToken = harness.account(meme).await;
+    harness.buy(&buyer, meme, sol_for_tokens(&state, launch_limit), 0, 100).await.unwrap();
+    harness.warp_seconds(1).await;
+    let state: MemeToken = harness.account(meme).await;
+    let held = harness.token_balance(&buyer.pubkey(), &meme).await;
+    assert_rejected(
+        harness.buy(&buyer, meme, sol_for_tokens(&state, TOTAL_SUPPLY / 50 - held + TOKENS), 0, 100).await,
+        ErrorCode::MaxWalletExceeded,
+    );
+}
+
+#[tokio::test]
+async fn slippage_and_amount_guards() {
+    let (mut harness, _, meme) = launched().await;
+    let buyer = harness.wallet(10 * SOL).await;
+    harness.warp_seconds(LAUNCH_COOLDOWN_SECONDS).await;
+
+    let state: MemeToken = harness.account(meme).await;
+    let quote = curve::quote_buy(state.virtual_sol_reserves, state.virtual_token_reserves, SOL / 10, u64::MAX).unwrap();
+    assert_rejected(harness.buy(&buyer, meme, SOL / 10, quote.tokens_out + 1, 100).await, ErrorCode::SlippageExceeded);
+    assert_rejected(harness.buy(&buyer, meme, SOL / 10, 0, 5_001).await, ErrorCode::SlippageTooHigh);
+    assert_rejected(harness.buy(&buyer, meme, 0, 0, 100).await, ErrorCode::InvalidAmount);
+    harness.buy(&buyer, meme, SOL / 10, quote.tokens_out, 100).await.unwrap();
+
+    harness.warp_seconds(1).await;
+    let state: MemeToken = harness.account(meme).await;
+    let sell = curve::quote_sell_on(&state.curve_position(), quote.tokens_out).unwrap();
+    assert_rejected(harness.sell(&buyer, meme, quote.tokens_out, sell.sol_out + 1, 100).await, ErrorCode::SlippageExceeded);
+    assert_rejected(harness.sell(&buyer, meme, quote.tokens_out, 0, 5_001).await, ErrorCode::SlippageTooHigh);
+    assert_rejected(harness.sell(&buyer, meme, 0, 0, 100).await, ErrorCode::InvalidAmount);
+    harness.sell(&buyer, meme, quote.tokens_out, sell.sol_out, 100).await.unwrap();
+}
+
+#[tokio::test]
+async fn launch_fee_decays_by_slot() {
+    let (mut harness, authority, meme) = launched().await;
+    let schedule = FeeSchedule { start_bps: 2_000, end_bps: 100, decay_slots: 100 };
+    let intruder = harness.wallet(SOL).await;
+    assert_rejected(harness.configure_fee_schedule(&intruder, schedule).await, ErrorCode::Unauthorized);
+    harness.configure_fee_schedule(&authority, schedule).await.unwrap();
+
+    let buyer = harness.wallet(10 * SOL).await;
+    harness.warp_seconds(LAUNCH_COOLDOWN_SECONDS).await;
+    harness.buy(&buyer, meme, SOL / 10, 0, 100).await.unwrap();
+    let protocol: Protocol = harness.account(pda(&[b"protocol"])).await;
+    let launch_fees = protocol.total_protocol_fees;
+    // The cooldown doesn't count toward the decay
+    assert_eq!(launch_fees, (SOL / 10 / 5) as u128);
+
+    harness.warp_slots(100).await;
+    harness.warp_seconds(1).await;
+    let later = harness.wallet(10 * SOL).await;
+    harness.buy(&later, meme, SOL / 10, 0, 100).await.unwrap();
+    let protocol: Protocol = harness.account(pda(&[b"protocol"])).await;
+    assert_eq!(protocol.total_protocol_fees - launch_fees, (SOL / 10 / 100) as u128);
+}
+
+#[tokio::test]
+async fn launches_are_validated() {
+    let mut harness = Harness::start().await;
+    let authority = harness.wallet(10 * SOL).await;
+    assert_rejected(harness.initialize_protocol(&authority, 1_001).await, ErrorCode::FeeTooHigh);
+    harness.initialize_protocol(&authority, 100).await.unwrap();
+    let creator = harness.wallet(10 * SOL).await;
+
+    let rejected = [
+        (Launch { image_hash: [0; 32], ..Launch::new(1) }, ErrorCode::InvalidImageHash),
+        (Launch { name: "M".repeat(33), ..Launch::new(1) }, ErrorCode::NameTooLong),
+        (Launch { name: String::new(), ..Launch::new(1) }, ErrorCode::NameTooShort),
+        (Launch { symbol: "LONGSYMBOL1".to_string(), ..Launch::new(1) }, ErrorCode::SymbolTooLong),
+        (Launch { symbol: String::new(), ..Launch::new(1) }, ErrorCode::SymbolTooShort),
+        (Launch { symbol: "meme".to_string(), ..Launch::new(1) }, ErrorCode::InvalidSymbolCharacters),
+        (Launch { initial_buy_sol: Some(SOL + 1), ..Launch::new(1) }, ErrorCode::DevBuyTooLarge),
+        // An allowlist phase that ends with the launch cooldown
+        (
+            Launch {
+                policy: LaunchPolicy {
+                    allowlist_root: [1; 32],
+                    allowlist_window_seconds: LAUNCH_COOLDOWN_SECONDS,
+                    ..LaunchPolicy::default()
+                },
+                ..Launch::new(1)
+            },
+            ErrorCode::InvalidLaunchPolicy,
+        ),
+    ];
+    for (launch, error) in rejected {
+        assert_rejected(harness.create_meme(&creator, launch).await.map(|_| ()), error);
+    }
+
+    // Dev-buy within the cap and the launch-period wallet limit
+    let meme = harness
+        .create_meme(&creator, Launch { initial_buy_sol: Some(SOL / 10), ..Launch::new(1) })
+        .await
+        .unwrap();
+    let state: MemeToken = harness.account(meme).await;
+    assert_eq!(harness.token_balance(&creator.pubkey(), &meme).await, state.dev_buy_tokens);
+    assert!(state.dev_buy_tokens > 0);
+
+    // Same art, different name
+    assert_rejected(
+        harness.create_meme(&creator, Launch { name: "Copycat".to_string(), ..Launch::new(1) }).await.map(|_| ()),
+        ErrorCode::DuplicateMeme,
+    );
+}
+
+#[tokio::test]
+async fn mint_authority_is_revoked_at_creation() {
+    let mut harness = Harness::start().await;
+    let authority = harness.wallet(10 * SOL).await;
+    harness.initialize_protocol(&authority, 100).await.unwrap();
+    let creator = harness.wallet(10 * SOL).await;
+
+    // With and without a dev-buy, the whole supply is minted once and no one
+    // can mint or freeze afterwards
+    for launch in [Launch::new(1), Launch { initial_buy_sol: Some(SOL / 10), ..Launch::new(2) }] {
+        let meme = harness.create_meme(&creator, launch).await.unwrap();
+        let mint: Mint = harness.account(pda(&[b"mint", meme.as_ref()])).await;
+        assert!(mint.mint_authority.is_none());
+        assert!(mint.freeze_authority.is_none());
+        assert_eq!(mint.supply, TOTAL_SUPPLY);
+    }
+}
+
+#[tokio::test]
+async fn dev_buys_are_capped_and_pay_the_protocol_fee() {
+    let mut harness = Harness::start().await;
+    let authority = harness.wallet(10 * SOL).await;
+    harness.initialize_protocol(&authority, 100).await.unwrap();
+    let creator = harness.wallet(10 * SOL).await;
+    let treasury = pda(&[b"treasury"]);
+
+    // Over max_initial_buy_lamports, then within it but over the
+    // launch-period wallet limit
+    for (sol, error) in [(SOL + 1, ErrorCode::DevBuyTooLarge), (SOL / 5, ErrorCode::MaxWalletExceeded)] {
+        let launch = Launch { initial_buy_sol: Some(sol), ..Launch::new(1) };
+        assert_rejected(harness.create_meme(&creator, launch).await.map(|_| ()), error);
+    }
+
+    // What a launch costs the creator without a dev-buy
+    let before = harness.lamports(creator.pubkey()).await;
+    let plain = harness.create_meme(&creator, Launch::new(1)).await.unwrap();
+    let launch_cost = before - harness.lamports(creator.pubkey()).await;
+
+    let sol = SOL / 10;
+    let protocol: Protocol = harness.account(pda(&[b"protocol"])).await;
+    let preset = protocol.curve_presets[0];
+    let quote = curve::quote_buy(preset.virtual_sol_reserves, preset.virtual_token_reserves, sol, u64::MAX).unwrap();
+    let before = harness.lamports(creator.pubkey()).await;
+    let treasury_before = harness.lamports(treasury).await;
+    let meme = harness.create_meme(&creator, Launch { initial_buy_sol: Some(sol), ..Launch::new(2) }).await.unwrap();
+
+    // The curve moved as if the creator bought first
+    let state: MemeToken = harness.account(meme).await;
+    assert_eq!((state.dev_buy_sol, state.dev_buy_tokens), (sol, quote.tokens_out));
+    assert_eq!(state.virtual_sol_reserves, quote.new_virtual_sol);
+    assert_eq!(state.virtual_token_reserves, quote.new_virtual_token);
+    assert_eq!(state.real_sol_reserves, sol);
+    let plain_state: MemeToken = harness.account(plain).await;
+    assert_eq!(state.real_token_reserves, plain_state.real_token_reserves - quote.tokens_out);
+    assert_eq!(state.circulating_supply, quote.tokens_out);
+    assert_eq!((state.holders_count, state.trade_count), (1, 1));
+    assert_eq!(harness.token_balance(&creator.pubkey(), &meme).await, quote.tokens_out);
+
+    // The creator paid the curve, the steady-state protocol fee and their
+    // wallet state's rent on top of the launch
+    let fee = sol / 100;
+    let wallet_state_rent = harness.lamports(pda(&[b"wallet", meme.as_ref(), creator.pubkey().as_ref()])).await;
+    let spent = before - harness.lamports(creator.pubkey()).await;
+    assert_eq!(spent, launch_cost + sol + fee + wallet_state_rent);
+    assert_eq!(harness.lamports(treasury).await, treasury_before + fee);
+    let protocol: Protocol = harness.account(pda(&[b"protocol"])).await;
+    assert_eq!(protocol.total_protocol_fees, fee as u128);
+    assert_eq!(protocol.total_buy_volume, sol as u128);
+    let vault_rent = harness.lamports(pda(&[b"vault", plain.as_ref()])).await;
+    assert_eq!(harness.lamports(pda(&[b"vault", meme.as_ref()])).await, vault_rent + sol);
+}
+
+#[tokio::test]
+async fn auction_claims_are_capped_at_the_launch_wallet_limit() {
+    let mut harness = Harness::start().await;
+    let authority = harness.wallet(10 * SOL).await;
+    harness.initialize_protocol(&authority, 100).await.unwrap();
+    let creator = harness.wallet(10 * SOL).await;
+    let policy = LaunchPolicy {
+        auction_window_slots: 10,
+        auction_tranche_bps: 5_000,
+        auction_max_bid_lamports: SOL,
+        ..LaunchPolicy::default()
+    };
+    let meme = harness.create_meme(&creator, Launch { policy, ..Launch::new(1) }).await.unwrap();
+    let vault = pda(&[b"vault", meme.as_ref()]);
+    let vault_rent = harness.lamports(vault).await;
+
+    // The whale's share of the tranche is ~7x the 0.5% launch limit
+    let whale = harness.wallet(10 * SOL).await;
+    let minnow = harness.wallet(10 * SOL).await;
+    harness.place_auction_bid(&whale, meme, SOL).await.unwrap();
+    harness.place_auction_bid(&minnow, meme, SOL / 100).await.unwrap();
+    assert_rejected(harness.settle_launch_auction(meme).await, ErrorCode::LaunchBatchNotReady);
+    harness.warp_slots(10).await;
+    assert_rejected(harness.place_auction_bid(&minnow, meme, 1).await, ErrorCode::AuctionWindowClosed);
+    harness.settle_launch_auction(meme).await.unwrap();
+
+    let settled: LaunchBatch = harness.account(pda(&[b"batch", meme.as_ref()])).await;
+    let share = |amount| batch::fill_order(amount, settled.total_revealed, settled.sol_filled, settled.tokens_filled).unwrap();
+    let launch_cap = TOTAL_SUPPLY / 200;
+    assert!(share(SOL).tokens > launch_cap);
+
+    harness.claim_batch_order(&whale, meme).await.unwrap();
+    harness.claim_batch_order(&minnow, meme).await.unwrap();
+    assert_eq!(harness.token_balance(&whale.pubkey(), &meme).await, launch_cap);
+    assert_eq!(harness.token_balance(&minnow.pubkey(), &meme).await, share(SOL / 100).tokens);
+
+    // The tokens over the cap went back to the curve and their cost back to the whale
+    let state: MemeToken = harness.account(meme).await;
+    let (capped, excess) = batch::cap_fill(share(SOL), launch_cap);
+    assert_eq!(state.circulating_supply, settled.tokens_filled - excess.tokens);
+    assert_eq!(state.real_sol_reserves, settled.sol_filled - excess.cost);
+    assert_eq!(harness.lamports(vault).await, vault_rent + state.real_sol_reserves);
+    let whale_spent = 10 * SOL - harness.lamports(whale.pubkey()).await;
+    assert!(whale_spent < capped.cost + capped.cost / 50 + SOL / 100);
+    let protocol: Protocol = harness.account(pda(&[b"protocol"])).await;
+    assert_eq!(protocol.total_volume, (capped.cost + share(SOL / 100).cost) as u128);
+}
+
+#[tokio::test]
+async fn commit_reveal_orders_fill_together() {
+    let mut harness = Harness::start().await;
+    let authority = harness.wallet(10 * SOL).await;
+    harness.initialize_protocol(&authority, 100).await.unwrap();
+    let creator = harness.wallet(10 * SOL).await;
+    let policy = LaunchPolicy { commit_window_slots: 10, reveal_window_slots: 10, ..LaunchPolicy::default() };
+    let meme = harness.create_meme(&creator, Launch { policy, ..Launch::new(1) }).await.unwrap();
+    let vault = pda(&[b"vault", meme.as_ref()]);
+    let vault_rent = harness.lamports(vault).await;
+
+    let whale = harness.wallet(10 * SOL).await;
+    let minnow = harness.wallet(10 * SOL).await;
+    let salt = [7; 32];
+    harness.commit_buy(&whale, meme, batch::commitment(&whale.pubkey(), SOL, &salt), 2 * SOL).await.unwrap();
+    // Escrows exactly the order, leaving nothing for its fees
+    harness.commit_buy(&minnow, meme, batch::commitment(&minnow.pubkey(), SOL / 100, &salt), SOL / 100).await.unwrap();
+    assert_rejected(harness.buy(&whale, meme, SOL / 10, 0, 100).await, ErrorCode::LaunchBatchPending);
+
+    harness.warp_slots(10).await;
+    harness.reveal_buy(&whale, meme, SOL, salt).await.unwrap();
+    assert_rejected(harness.reveal_buy(&minnow, meme, SOL / 100, salt).await, ErrorCode::EscrowTooSmall);
+    assert_rejected(harness.settle_launch_batch(meme).await, ErrorCode::LaunchBatchNotReady);
+    harness.warp_slots(10).await;
+    harness.settle_launch_batch(meme).await.unwrap();
+
+    // The whale's fill is over the launch wallet limit and gets capped
+    harness.claim_batch_order(&whale, meme).await.unwrap();
+    assert_eq!(harness.token_balance(&whale.pubkey(), &meme).await, TOTAL_SUPPLY / 200);
+    let state: MemeToken = harness.account(meme).await;
+    assert!(!state.launch_batch_pending);
+    assert_eq!(state.circulating_supply, TOTAL_SUPPLY / 200);
+    assert_eq!(harness.lamports(vault).await, vault_rent + state.real_sol_reserves);
+
+    // An order that was never revealed gets its escrow and rent back, less
+    // the rent of the accounts every claim opens
+    harness.claim_batch_order(&minnow, meme).await.unwrap();
+    assert!(!harness.exists(pda(&[b"order", meme.as_ref(), minnow.pubkey().as_ref()])).await);
+    let mint = pda(&[b"mint", meme.as_ref()]);
+    let opened = harness.lamports(get_associated_token_address(&minnow.pubkey(), &mint)).await
+        + harness.lamports(pda(&[b"wallet", meme.as_ref(), minnow.pubkey().as_ref()])).await;
+    assert_eq!(harness.lamports(minnow.pubkey()).await, 10 * SOL - opened);
+}
+
+#[tokio::test]
+async fn batch_orders_are_held_to_the_allowlist() {
+    let mut harness = Harness::start().await;
+    let authority = harness.wallet(10 * SOL).await;
+    harness.initialize_protocol(&authority, 100).await.unwrap();
+    let creator = harness.wallet(10 * SOL).await;
+    let buyer = harness.wallet(2 * SOL).await;
+    let stranger = harness.wallet(2 * SOL).await;
+    let entry = AllowlistEntry { cap_lamports: SOL / 10, proof: vec![] };
+    let allowlisted = LaunchPolicy {
+        allowlist_root: allowlist::leaf(&buyer.pubkey(), SOL / 10),
+        allowlist_window_seconds: 600,
+        ..LaunchPolicy::default()
+    };
+    let commit_reveal = LaunchPolicy { commit_window_slots: 10, reveal_window_slots: 10, ..allowlisted };
+    let auction = LaunchPolicy {
+        auction_window_slots: 10,
+        auction_tranche_bps: 1_000,
+        auction_max_bid_lamports: SOL,
+        ..allowlisted
+    };
+    let committed = harness.create_meme(&creator, Launch { policy: commit_reveal, ..Launch::new(1) }).await.unwrap();
+    let auctioned = harness.create_meme(&creator, Launch { policy: auction, ..Launch::new(2) }).await.unwrap();
+
+    // Off-list wallets can't commit or bid, listed ones only up to their cap
+    let salt = [7; 32];
+    let order = |wallet: &Keypair| batch::commitment(&wallet.pubkey(), SOL / 20, &salt);
+    assert_rejected(harness.commit_buy(&stranger, committed, order(&stranger), SOL).await, ErrorCode::NotAllowlisted);
+    harness.commit_buy_with(&buyer, committed, order(&buyer), SOL, Some(entry.clone())).await.unwrap();
+    assert_rejected(harness.place_auction_bid(&stranger, auctioned, SOL / 20).await, ErrorCode::NotAllowlisted);
+    assert_rejected(
+        harness.place_auction_bid_with(&buyer, auctioned, SOL / 5, Some(entry.clone())).await,
+        ErrorCode::AllowlistCapExceeded,
+    );
+    harness.place_auction_bid_with(&buyer, auctioned, SOL / 20, Some(entry)).await.unwrap();
+
+    // The claimed order counts against the cap for the rest of the window
+    harness.warp_slots(10).await;
+    harness.reveal_buy(&buyer, committed, SOL / 20, salt).await.unwrap();
+    harness.warp_slots(10).await;
+    harness.settle_launch_batch(committed).await.unwrap();
+    harness.claim_batch_order(&buyer, committed).await.unwrap();
+    let settled: LaunchBatch = harness.account(pda(&[b"batch", committed.as_ref()])).await;
+    let wallet_state: WalletState = harness.account(pda(&[b"wallet", committed.as_ref(), buyer.pubkey().as_ref()])).await;
+    assert_eq!(wallet_state.allowlist_spent, settled.sol_filled);
+}
+
+#[tokio::test]
+async fn denied_wallets_stay_out_of_launch_batches() {
+    let mut harness = Harness::start().await;
+    let authority = harness.wallet(10 * SOL).await;
+    harness.initialize_protocol(&authority, 100).await.unwrap();
+    let creator = harness.wallet(10 * SOL).await;
+    let denied = harness.wallet(2 * SOL).await;
+    let buyer = harness.wallet(2 * SOL).await;
+    let commit_reveal = LaunchPolicy {
+        commit_window_slots: 10,
+        reveal_window_slots: 10,
+        denylist_enabled: true,
+        ..LaunchPolicy::default()
+    };
+    let auction = LaunchPolicy {
+        auction_window_slots: 10,
+        auction_tranche_bps: 1_000,
+        auction_max_bid_lamports: SOL,
+        denylist_enabled: true,
+        ..LaunchPolicy::default()
+    };
+    let committed = harness.create_meme(&creator, Launch { policy: commit_reveal, ..Launch::new(1) }).await.unwrap();
+    let auctioned = harness.create_meme(&creator, Launch { policy: auction, ..Launch::new(2) }).await.unwrap();
+    for meme in [committed, auctioned] {
+        harness.update_denylist(&creator, meme, vec![denied.pubkey()], vec![]).await.unwrap();
+    }
+
+    let salt = [7; 32];
+    let order = |wallet: &Keypair| batch::commitment(&wallet.pubkey(), SOL / 20, &salt);
+    assert_rejected(harness.commit_buy(&denied, committed, order(&denied), SOL).await, ErrorCode::WalletDenylisted);
+    assert_rejected(harness.place_auction_bid(&denied, auctioned, SOL / 20).await, ErrorCode::WalletDenylisted);
+    harness.commit_buy(&buyer, committed, order(&buyer), SOL).await.unwrap();
+    harness.place_auction_bid(&buyer, auctioned, SOL / 20).await.unwrap();
+
+    // A wallet denied after ordering can only claim once the launch period is over
+    harness.warp_slots(10).await;
+    harness.reveal_buy(&buyer, committed, SOL / 20, salt).await.unwrap();
+    harness.update_denylist(&creator, committed, vec![buyer.pubkey()], vec![]).await.unwrap();
+    harness.warp_slots(10).await;
+    harness.settle_launch_batch(committed).await.unwrap();
+    assert_rejected(harness.claim_batch_order(&buyer, committed).await, ErrorCode::WalletDenylisted);
+    harness.warp_seconds(LAUNCH_PERIOD_SECONDS).await;
+    harness.claim_batch_order(&buyer, committed).await.unwrap();
+}
+
+#[tokio::test]
+async fn launch_gates_turn_buyers_away() {
+    let mut harness = Harness::start().await;
+    let authority = harness.wallet(10 * SOL).await;
+    harness.initialize_protocol(&authority, 100).await.unwrap();
+    let attester = harness.wallet(SOL).await;
+    harness.configure_attester(&authority, attester.pubkey()).await.unwrap();
+    let creator = harness.wallet(10 * SOL).await;
+    let buyer = harness.wallet(2 * SOL).await;
+    let stranger = harness.wallet(2 * SOL).await;
+
+    let policies = [
+        LaunchPolicy {
+            allowlist_root: allowlist::leaf(&buyer.pubkey(), SOL / 10),
+            allowlist_window_seconds: 600,
+            ..LaunchPolicy::default()
+        },
+        LaunchPolicy { denylist_enabled: true, ..LaunchPolicy::default() },
+        LaunchPolicy { require_attestation: true, ..LaunchPolicy::default() },
+        LaunchPolicy { min_buyer_balance_lamports: 5 * SOL, ..LaunchPolicy::default() },
+    ];
+    let mut memes = Vec::new();
+    for (seed, policy) in (1..).zip(policies) {
+        memes.push(harness.create_meme(&creator, Launch { policy, ..Launch::new(seed) }).await.unwrap());
+    }
+    let [allowlisted, denylisted, attested, well_funded] = memes[..] else { unreachable!() };
+    harness.update_denylist(&creator, denylisted, vec![buyer.pubkey()], vec![]).await.unwrap();
+    harness.warp_seconds(LAUNCH_COOLDOWN_SECONDS).await;
+
+    assert_rejected(harness.buy(&stranger, allowlisted, SOL / 20, 0, 100).await, ErrorCode::NotAllowlisted);
+    let entry = AllowlistEntry { cap_lamports: SOL / 10, proof: vec![] };
+    harness.buy_with(&buyer, allowlisted, SOL / 20, 0, 100, None, Some(entry)).await.unwrap();
+
+    assert_rejected(harness.buy(&buyer, denylisted, SOL / 20, 0, 100).await, ErrorCode::WalletDenylisted);
+    harness.buy(&stranger, denylisted, SOL / 20, 0, 100).await.unwrap();
+
+    assert_rejected(harness.buy(&buyer, attested, SOL / 20, 0, 100).await, ErrorCode::AttestationRequired);
+    let expires_at = harness.clock().await.unix_timestamp + 3_600;
+    harness.issue_attestation(&attester, buyer.pubkey(), expires_at).await.unwrap();
+    harness.buy(&buyer, attested, SOL / 20, 0, 100).await.unwrap();
+
+    assert_rejected(harness.buy(&buyer, well_funded, SOL / 20, 0, 100).await, ErrorCode::BuyerBalanceTooLow);
+}
+
+#[tokio::test]
+async fn launch_gates_hold_for_batch_orders() {
+    let mut harness = Harness::start().await;
+    let authority = harness.wallet(10 * SOL).await;
+    harness.initialize_protocol(&authority, 100).await.unwrap();
+    let attester = harness.wallet(SOL).await;
+    harness.configure_attester(&authority, attester.pubkey()).await.unwrap();
+    let creator = harness.wallet(10 * SOL).await;
+    let buyer = harness.wallet(2 * SOL).await;
+    let attested = LaunchPolicy {
+        commit_window_slots: 10,
+        reveal_window_slots: 10,
+        require_attestation: true,
+        ..LaunchPolicy::default()
+    };
+    let well_funded = LaunchPolicy {
+        auction_window_slots: 10,
+        auction_tranche_bps: 1_000,
+        auction_max_bid_lamports: SOL,
+        min_buyer_balance_lamports: SOL,
+        ..LaunchPolicy::default()
+    };
+    let committed = harness.create_meme(&creator, Launch { policy: attested, ..Launch::new(1) }).await.unwrap();
+    let auctioned = harness.create_meme(&creator, Launch { policy: well_funded, ..Launch::new(2) }).await.unwrap();
+
+    let commitment = batch::commitment(&buyer.pubkey(), SOL / 20, &[7; 32]);
+    assert_rejected(harness.commit_buy(&buyer, committed, commitment, SOL / 10).await, ErrorCode::AttestationRequired);
+    let expires_at = harness.clock().await.unix_timestamp + 3_600;
+    harness.issue_attestation(&attester, buyer.pubkey(), expires_at).await.unwrap();
+    harness.commit_buy(&buyer, committed, commitment, SOL / 10).await.unwrap();
+
+    // The balance is the one left once the bid is in escrow
+    assert_rejected(harness.place_auction_bid(&buyer, auctioned, SOL).await, ErrorCode::BuyerBalanceTooLow);
+    harness.place_auction_bid(&buyer, auctioned, SOL / 20).await.unwrap();
+}
+
+#[tokio::test]
+async fn sold_out_curves_and_underfunded_vaults_are_refused() {
+    let (mut harness, _, meme) = launched().await;
+    let buyer = harness.wallet(10 * SOL).await;
+    harness.warp_seconds(LAUNCH_COOLDOWN_SECONDS).await;
+    harness.buy(&buyer, meme, SOL / 10, 0, 100).await.unwrap();
+    harness.warp_seconds(1).await;
+
+    // Reserves the vault no longer backs: a sell can't take it below rent
+    let vault = pda(&[b"vault", meme.as_ref()]);
+    let state: MemeToken = harness.account(meme).await;
+    let vault_lamports = harness.lamports(vault).await;
+    harness.set_lamports(vault, vault_lamports - state.real_sol_reserves).await;
+    let held = harness.token_balance(&buyer.pubkey(), &meme).await;
+    assert_rejected(harness.sell(&buyer, meme, held, 0, 100).await, ErrorCode::VaultUnderfunded);
+
+    // A curve with nothing left to sell that somehow didn't graduate
+    harness
+        .edit_account(meme, |state: &mut MemeToken| state.circulating_supply = state.bonding_curve_supply)
+        .await;
+    assert_rejected(harness.buy(&buyer, meme, SOL / 10, 0, 100).await, ErrorCode::CurveSoldOut);
+}
+
+#[tokio::test]
+async fn referrals_pay_out_but_not_to_the_trader() {
+    let (mut harness, authority, meme) = launched().await;
+    harness.configure_referral_share(&authority, 5_000).await.unwrap();
+    let referrer = harness.wallet(SOL).await;
+    harness.register_referrer(&referrer).await.unwrap();
+    assert_rejected(harness.claim_referral_fees(&referrer).await, ErrorCode::NothingToClaim);
+    harness.warp_seconds(LAUNCH_COOLDOWN_SECONDS).await;
+
+    let referrer_key = Some(referrer.pubkey());
+    assert_rejected(
+        harness.buy_with(&referrer, meme, SOL / 20, 0, 100, referrer_key, None).await,
+        ErrorCode::SelfReferral,
+    );
+    let buyer = harness.wallet(SOL).await;
+    harness.buy_with(&buyer, meme, SOL / 10, 0, 100, referrer_key, None).await.unwrap();
+
+    let state: MemeToken = harness.account(meme).await;
+    let fees = curve::trade_fees(SOL / 10, 100, state.creator_fee_bps).unwrap().with_referral(5_000).unwrap();
+    let referrer_state: ReferrerState = harness.account(pda(&[b"referrer", referrer.pubkey().as_ref()])).await;
+    assert_eq!(referrer_state.unclaimed, fees.referral_fee);
+    let before = harness.lamports(referrer.pubkey()).await;
+    harness.claim_referral_fees(&referrer).await.unwrap();
+    assert_eq!(harness.lamports(referrer.pubkey()).await - before, fees.referral_fee);
+}
+
+#[tokio::test]
+async fn quotes_report_what_the_buy_would_do() {
+    let (mut harness, _, meme) = launched().await;
+    let buyer = harness.wallet(10 * SOL).await;
+    harness.warp_seconds(1).await;
+    let early = harness.quote_buy(buyer.pubkey(), meme, SOL / 10).await;
+    assert_eq!(early.rejection, Some(u32::from(ErrorCode::LaunchCooldownActive)));
+
+    harness.warp_seconds(LAUNCH_COOLDOWN_SECONDS).await;
+    let quote = harness.quote_buy(buyer.pubkey(), meme, SOL / 10).await;
+    assert_eq!(quote.rejection, None);
+    harness.buy(&buyer, meme, SOL / 10, quote.amount_out, 0).await.unwrap();
+    assert_eq!(harness.token_balance(&buyer.pubkey(), &meme).await, quote.amount_out);
+    let protocol: Protocol = harness.account(pda(&[b"protocol"])).await;
+    assert_eq!(protocol.total_protocol_fees, quote.protocol_fee as u128);
+}
+
+#[tokio::test]
+async fn quotes_report_curves_that_stopped_trading() {
+    let (mut harness, _, meme) = launched().await;
+    let buyer = harness.wallet(10 * SOL).await;
+    harness.warp_seconds(LAUNCH_COOLDOWN_SECONDS).await;
+    harness.buy(&buyer, meme, SOL / 10, 0, 100).await.unwrap();
+    harness.warp_seconds(1).await;
+    let held = harness.token_balance(&buyer.pubkey(), &meme).await;
+
+    harness
+        .edit_account(meme, |state: &mut MemeToken| state.circulating_supply = state.bonding_curve_supply)
+        .await;
+    let sold_out = harness.quote_buy(buyer.pubkey(), meme, SOL / 10).await;
+    assert_eq!(sold_out.rejection, Some(u32::from(ErrorCode::CurveSoldOut)));
+    assert_eq!(sold_out.amount_out, 0);
+
+    harness.edit_account(meme, |state: &mut MemeToken| state.is_graduated = true).await;
+    let buy = harness.quote_buy(buyer.pubkey(), meme, SOL / 10).await;
+    assert_eq!(buy.rejection, Some(u32::from(ErrorCode::AlreadyGraduated)));
+    let sell = harness.quote_sell(buyer.pubkey(), meme, held).await;
+    assert_eq!(sell.rejection, Some(u32::from(ErrorCode::AlreadyGraduated)));
+    assert_eq!(sell.amount_out, 0);
+}
+
+#[tokio::test]
+async fn quotes_count_the_rent_a_first_buy_pays() {
+    let mut harness = Harness::start().await;
+    let authority = harness.wallet(10 * SOL).await;
+    harness.initialize_protocol(&authority, 100).await.unwrap();
+    let creator = harness.wallet(10 * SOL).await;
+    let policy = LaunchPolicy { min_buyer_balance_lamports: SOL, ..LaunchPolicy::default() };
+    let meme = harness.create_meme(&creator, Launch { policy, ..Launch::new(1) }).await.unwrap();
+    harness.warp_seconds(LAUNCH_COOLDOWN_SECONDS).await;
+
+    // Enough to stay over the minimum after the buy and its fees, but not
+    // after the rent for the buyer's wallet state and token account
+    let state: MemeToken = harness.account(meme).await;
+    let fees = curve::trade_fees(SOL / 10, 100, state.creator_fee_bps).unwrap().total().unwrap();
+    let buyer = harness.wallet(SOL + SOL / 10 + fees + 1_000_000).await;
+    let quote = harness.quote_buy(buyer.pubkey(), meme, SOL / 10).await;
+    assert_eq!(quote.rejection, Some(u32::from(ErrorCode::BuyerBalanceTooLow)));
+    assert_rejected(harness.buy(&buyer, meme, SOL / 10, 0, 100).await, ErrorCode::BuyerBalanceTooLow);
+}
+
+#[tokio::test]
+async fn protocol_fees_are_withdrawn_by_bucket() {
+    let (mut harness, authority, meme) = launched().await;
+    let buyer = harness.wallet(10 * SOL).await;
+    harness.warp_seconds(LAUNCH_COOLDOWN_SECONDS).await;
+    harness.buy(&buyer, meme, SOL / 10, 0, 100).await.unwrap();
+
+    // All of it lands in the team bucket under the default split
+    let fee = SOL / 10 / 100;
+    let recipient = harness.wallet(SOL).await.pubkey();
+    let intruder = harness.wallet(SOL).await;
+    assert_rejected(
+        harness.withdraw_protocol_fees(&intruder, recipient, fee, TreasuryBucket::Team).await,
+        ErrorCode::Unauthorized,
+    );
+    assert_rejected(
+        harness.withdraw_protocol_fees(&authority, recipient, fee, TreasuryBucket::Buyback).await,
+        ErrorCode::InsufficientFunds,
+    );
+    assert_rejected(
+        harness.withdraw_protocol_fees(&authority, recipient, fee + 1, TreasuryBucket::Team).await,
+        ErrorCode::InsufficientFunds,
+    );
+    harness.withdraw_protocol_fees(&authority, recipient, fee, TreasuryBucket::Team).await.unwrap();
+    assert_eq!(harness.lamports(recipient).await, SOL + fee);
+}
+
+#[tokio::test]
+async fn custom_curves_and_vesting_are_bounded() {
+    let mut harness = Harness::start().await;
+    let authority = harness.wallet(10 * SOL).await;
+    harness.initialize_protocol(&authority, 100).await.unwrap();
+    let creator = harness.wallet(10 * SOL).await;
+
+    // Below the default 10 SOL floor for custom reserves, and a shaped curve
+    // starting below the cheapest price those bounds allow
+    let cheap_reserves = InitialReserves::Custom {
+        virtual_sol_reserves: 5 * SOL,
+        virtual_token_reserves: 1_073_000_000 * TOKENS,
+    };
+    let cheap_curve = CurveType::Linear { start_price: SOL, end_price: 400 * SOL };
+    for launch in [
+        Launch { reserves: cheap_reserves, ..Launch::new(1) },
+        Launch { curve_type: cheap_curve, ..Launch::new(1) },
+    ] {
+        assert_rejected(harness.create_meme(&creator, launch).await.map(|_| ()), ErrorCode::ReservesOutOfBounds);
+    }
+
+    // Nothing vests before the cliff
+    let vesting = CreatorVestingParams {
+        allocation_bps: 500,
+        cliff_seconds: 7 * 86_400,
+        duration_seconds: 30 * 86_400,
+    };
+    let meme = harness.create_meme(&creator, Launch { vesting: Some(vesting), ..Launch::new(1) }).await.unwrap();
+    assert_rejected(harness.claim_vested(&creator, meme).await, ErrorCode::NothingToClaim);
+}
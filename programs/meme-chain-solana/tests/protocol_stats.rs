@@ -0,0 +1,23 @@
+use meme_chain_solana::Protocol;
+
+#[test]
+fn volume_counts_trade_size_not_fees() {
+    let mut protocol = Protocol::default();
+    protocol.record_trade(true, 1_000_000_000, 10_000_000, 5_000_000).unwrap();
+    protocol.record_trade(false, 400_000_000, 4_000_000, 2_000_000).unwrap();
+
+    assert_eq!(protocol.total_buy_volume, 1_000_000_000);
+    assert_eq!(protocol.total_sell_volume, 400_000_000);
+    assert_eq!(protocol.total_volume, 1_400_000_000);
+    assert_eq!(protocol.total_protocol_fees, 14_000_000);
+    assert_eq!(protocol.total_creator_fees, 7_000_000);
+    assert_eq!(protocol.total_trades, 2);
+}
+
+#[test]
+fn volume_does_not_overflow_past_u64() {
+    let mut protocol = Protocol::default();
+    protocol.record_trade(true, u64::MAX, 0, 0).unwrap();
+    protocol.record_trade(false, u64::MAX, 0, 0).unwrap();
+    assert_eq!(protocol.total_volume, 2 * u64::MAX as u128);
+}
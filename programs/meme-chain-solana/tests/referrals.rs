@@ -0,0 +1,27 @@
+use meme_chain_solana::curve::trade_fees;
+use proptest::prelude::*;
+
+#[test]
+fn referral_share_comes_out_of_the_protocol_fee() {
+    // 1% protocol fee, 0.5% creator fee, referrer gets 20% of the protocol fee
+    let fees = trade_fees(1_000_000_000, 100, 50).unwrap().with_referral(2_000).unwrap();
+    assert_eq!(fees.protocol_fee, 8_000_000);
+    assert_eq!(fees.referral_fee, 2_000_000);
+    assert_eq!(fees.creator_fee, 5_000_000);
+    assert_eq!(fees.total().unwrap(), 15_000_000);
+}
+
+proptest! {
+    #[test]
+    fn referral_never_changes_what_the_trader_pays(
+        amount in 0u64..=u64::MAX / 2,
+        protocol_bps in 0u16..=1_000,
+        creator_bps in 0u16..=1_000,
+        share_bps in 0u16..=5_000,
+    ) {
+        let fees = trade_fees(amount, protocol_bps, creator_bps).unwrap();
+        let referred = fees.with_referral(share_bps).unwrap();
+        prop_assert_eq!(referred.total().unwrap(), fees.total().unwrap());
+        prop_assert!(referred.referral_fee <= fees.protocol_fee / 2);
+    }
+}
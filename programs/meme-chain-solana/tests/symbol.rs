@@ -0,0 +1,22 @@
+use meme_chain_solana::symbol::normalize;
+
+#[test]
+fn case_variants_share_a_reservation() {
+    assert_eq!(normalize("doge"), normalize("DOGE"));
+    assert_eq!(normalize("DoGe"), "DOGE");
+}
+
+#[test]
+fn confusables_and_invisible_characters_are_folded() {
+    assert_eq!(normalize("D0GE"), normalize("DOGE"));
+    assert_eq!(normalize("DОGE"), normalize("DOGE")); // Cyrillic О
+    assert_eq!(normalize("D\u{200B}OGE"), normalize("DOGE")); // zero-width space
+    assert_eq!(normalize("D.O-G E"), normalize("DOGE"));
+    assert_eq!(normalize("PEPE1"), normalize("PEPEl"));
+}
+
+#[test]
+fn distinct_symbols_stay_distinct() {
+    assert_ne!(normalize("DOGE"), normalize("PEPE"));
+    assert!(normalize("\u{200B}\u{202E}").is_empty());
+}
@@ -0,0 +1,37 @@
+use meme_chain_solana::{ProtocolTreasury, TreasurySplit};
+use proptest::prelude::*;
+
+const SPLIT: TreasurySplit = TreasurySplit {
+    team_bps: 5_000,
+    buyback_bps: 3_333,
+    insurance_bps: 1_667,
+};
+
+#[test]
+fn accrual_fills_buckets_by_split() {
+    let mut treasury = ProtocolTreasury {
+        team_balance: 0,
+        buyback_balance: 0,
+        insurance_balance: 0,
+        total_collected: 0,
+        total_withdrawn: 0,
+        bump: 255,
+    };
+    treasury.accrue(1_000_000, &SPLIT).unwrap();
+    treasury.accrue(1_000_000, &SPLIT).unwrap();
+
+    assert_eq!(treasury.team_balance, 1_000_000);
+    assert_eq!(treasury.buyback_balance, 666_600);
+    assert_eq!(treasury.insurance_balance, 333_400);
+    assert_eq!(treasury.total_collected, 2_000_000);
+}
+
+proptest! {
+    #[test]
+    fn buckets_always_add_up_to_the_fee(amount in any::<u64>(), team in 0u16..=10_000, buyback in 0u16..=10_000) {
+        let buyback = buyback.min(10_000 - team);
+        let split = TreasurySplit { team_bps: team, buyback_bps: buyback, insurance_bps: 10_000 - team - buyback };
+        let (t, b, i) = split.allocate(amount).unwrap();
+        prop_assert_eq!(t as u128 + b as u128 + i as u128, amount as u128);
+    }
+}
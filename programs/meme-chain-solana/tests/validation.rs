@@ -0,0 +1,32 @@
+use meme_chain_solana::validation::{validate_name, validate_symbol, validate_uri};
+
+#[test]
+fn symbol_must_be_uppercase_alphanumeric() {
+    assert!(validate_symbol("DOGE").is_ok());
+    assert!(validate_symbol("PEPE2").is_ok());
+    assert!(validate_symbol("doge").is_err());
+    assert!(validate_symbol("DO GE").is_err());
+    assert!(validate_symbol("DОGE").is_err()); // Cyrillic О
+    assert!(validate_symbol("$WIF").is_err());
+}
+
+#[test]
+fn name_rejects_control_and_invisible_characters() {
+    assert!(validate_name("Doge Supreme 🐶").is_ok());
+    assert!(validate_name("Dogé").is_ok());
+    assert!(validate_name("Doge\u{200B}coin").is_err());
+    assert!(validate_name("\u{202E}egoD").is_err());
+    assert!(validate_name("Doge\n").is_err());
+    assert!(validate_name("Do\u{2066}ge").is_err());
+}
+
+#[test]
+fn uri_requires_allowlisted_scheme() {
+    assert!(validate_uri("https://arweave.net/meta.json").is_ok());
+    assert!(validate_uri("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi").is_ok());
+    assert!(validate_uri("ar://abc123").is_ok());
+    assert!(validate_uri("http://example.com/meta.json").is_err());
+    assert!(validate_uri("javascript:alert(1)").is_err());
+    assert!(validate_uri("https://").is_err());
+    assert!(validate_uri("https://exa mple.com").is_err());
+}
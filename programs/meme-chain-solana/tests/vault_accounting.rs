@@ -0,0 +1,147 @@
+mod harness;
+
+use harness::{pda, Harness, Launch, SOL};
+use meme_chain_solana::{curve, LaunchPolicy, MemeToken};
+use proptest::prelude::*;
+use solana_sdk::signature::Signer;
+
+const LAUNCH_COOLDOWN_SECONDS: i64 = 60;
+const TRADERS: usize = 3;
+// Rent-exempt minimum of the BondingCurveVault account (8 + 33 bytes)
+const VAULT_RENT: u64 = 1_176_240;
+
+#[derive(Clone, Debug)]
+enum Step {
+    Buy { trader: usize, lamports: u64 },
+    Sell { trader: usize, share_bps: u64 },
+}
+
+fn step() -> impl Strategy<Value = Step> {
+    prop_oneof![
+        (0..TRADERS, 1u64..=SOL / 20).prop_map(|(trader, lamports)| Step::Buy { trader, lamports }),
+        (0..TRADERS, 1u64..=10_000).prop_map(|(trader, share_bps)| Step::Sell { trader, share_bps }),
+    ]
+}
+
+// Runs `steps` through buy_tokens / sell_tokens on a fresh launch. Steps the
+// program rejects, like a buy over the launch wallet limit, are part of the
+// sequence too: the vault has to stay in lockstep either way.
+async fn vault_tracks_reserves(steps: Vec<Step>) -> Result<(), TestCaseError> {
+    let mut harness = Harness::start().await;
+    let authority = harness.wallet(10 * SOL).await;
+    harness.initialize_protocol(&authority, 100).await.unwrap();
+    let creator = harness.wallet(10 * SOL).await;
+    let meme = harness.create_meme(&creator, Launch::new(1)).await.unwrap();
+    let vault = pda(&[b"vault", meme.as_ref()]);
+    let vault_rent = harness.lamports(vault).await;
+    let mut traders = Vec::new();
+    for _ in 0..TRADERS {
+        traders.push(harness.wallet(10 * SOL).await);
+    }
+    harness.warp_seconds(LAUNCH_COOLDOWN_SECONDS).await;
+
+    for step in steps {
+        match step {
+            Step::Buy { trader, lamports } => {
+                let _ = harness.buy(&traders[trader], meme, lamports, 0, 5_000).await;
+            }
+            Step::Sell { trader, share_bps } => {
+                let balance = harness.token_balance(&traders[trader].pubkey(), &meme).await;
+                let tokens = balance * share_bps / 10_000;
+                if tokens > 0 {
+                    let _ = harness.sell(&traders[trader], meme, tokens, 0, 5_000).await;
+                }
+            }
+        }
+        let state: MemeToken = harness.account(meme).await;
+        prop_assert_eq!(
+            harness.lamports(vault).await,
+            curve::expected_vault_lamports(vault_rent, state.real_sol_reserves).unwrap()
+        );
+        harness.warp_seconds(1).await;
+    }
+    Ok(())
+}
+
+// Settles an auction of `bids` and claims every order. Claims over the launch
+// wallet limit hand tokens back to the curve, which has to leave the real
+// and virtual reserves moved by the same amounts as the vault.
+async fn auction_claims_keep_reserves_in_step(tranche_bps: u16, bids: Vec<(usize, u64)>) -> Result<(), TestCaseError> {
+    let mut harness = Harness::start().await;
+    let authority = harness.wallet(10 * SOL).await;
+    harness.initialize_protocol(&authority, 100).await.unwrap();
+    let creator = harness.wallet(10 * SOL).await;
+    let policy = LaunchPolicy {
+        auction_window_slots: 10,
+        auction_tranche_bps: tranche_bps,
+        auction_max_bid_lamports: SOL,
+        ..LaunchPolicy::default()
+    };
+    let meme = harness.create_meme(&creator, Launch { policy, ..Launch::new(1) }).await.unwrap();
+    let vault = pda(&[b"vault", meme.as_ref()]);
+    let vault_rent = harness.lamports(vault).await;
+    let launched: MemeToken = harness.account(meme).await;
+    let mut bidders = Vec::new();
+    for _ in 0..TRADERS {
+        bidders.push(harness.wallet(10 * SOL).await);
+    }
+
+    for &(bidder, lamports) in &bids {
+        harness.place_auction_bid(&bidders[bidder], meme, lamports).await.unwrap();
+    }
+    harness.warp_slots(10).await;
+    harness.settle_launch_auction(meme).await.unwrap();
+
+    for (index, bidder) in bidders.iter().enumerate() {
+        if bids.iter().any(|&(who, _)| who == index) {
+            harness.claim_batch_order(bidder, meme).await.unwrap();
+        }
+        let state: MemeToken = harness.account(meme).await;
+        prop_assert_eq!(
+            harness.lamports(vault).await,
+            curve::expected_vault_lamports(vault_rent, state.real_sol_reserves).unwrap()
+        );
+        prop_assert_eq!(
+            state.virtual_sol_reserves - launched.virtual_sol_reserves,
+            state.real_sol_reserves - launched.real_sol_reserves
+        );
+        prop_assert_eq!(
+            launched.virtual_token_reserves - state.virtual_token_reserves,
+            launched.real_token_reserves - state.real_token_reserves
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn withdrawal_cannot_underflow_the_vault() {
+    assert!(curve::vault_balance_after_withdrawal(VAULT_RENT, VAULT_RENT + 1, VAULT_RENT).is_err());
+    assert!(curve::vault_balance_after_withdrawal(0, 1, 0).is_err());
+}
+
+#[test]
+fn withdrawal_cannot_breach_rent_exemption() {
+    let vault = VAULT_RENT + 1_000;
+    assert_eq!(curve::vault_balance_after_withdrawal(vault, 1_000, VAULT_RENT).unwrap(), VAULT_RENT);
+    assert!(curve::vault_balance_after_withdrawal(vault, 1_001, VAULT_RENT).is_err());
+}
+
+proptest! {
+    // Each case starts its own bank, so keep the count down
+    #![proptest_config(ProptestConfig::with_cases(8))]
+
+    #[test]
+    fn vault_always_holds_rent_plus_reserves(steps in prop::collection::vec(step(), 1..24)) {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(vault_tracks_reserves(steps))?;
+    }
+
+    #[test]
+    fn auction_claims_move_real_and_virtual_reserves_together(
+        tranche_bps in 1_000u16..=5_000,
+        bids in prop::collection::vec((0..TRADERS, 1u64..=SOL), 1..8),
+    ) {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(auction_claims_keep_reserves_in_step(tranche_bps, bids))?;
+    }
+}
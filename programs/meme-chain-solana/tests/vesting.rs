@@ -0,0 +1,24 @@
+use meme_chain_solana::vesting::vested_amount;
+
+const DAY: i64 = 86_400;
+const TOTAL: u64 = 50_000_000_000_000; // 5% of 1B tokens
+
+#[test]
+fn nothing_unlocks_before_cliff() {
+    assert_eq!(vested_amount(TOTAL, 0, 7 * DAY, 30 * DAY, 0), 0);
+    assert_eq!(vested_amount(TOTAL, 0, 7 * DAY, 30 * DAY, 7 * DAY - 1), 0);
+    // Clock before start (should never happen, must not panic)
+    assert_eq!(vested_amount(TOTAL, 100, 7 * DAY, 30 * DAY, 0), 0);
+}
+
+#[test]
+fn releases_linearly_from_start_after_cliff() {
+    assert_eq!(vested_amount(TOTAL, 0, 7 * DAY, 30 * DAY, 7 * DAY), TOTAL * 7 / 30);
+    assert_eq!(vested_amount(TOTAL, 0, 7 * DAY, 30 * DAY, 15 * DAY), TOTAL / 2);
+}
+
+#[test]
+fn fully_vested_at_end_of_duration() {
+    assert_eq!(vested_amount(TOTAL, 0, 7 * DAY, 30 * DAY, 30 * DAY), TOTAL);
+    assert_eq!(vested_amount(TOTAL, 0, 7 * DAY, 30 * DAY, i64::MAX), TOTAL);
+}
@@ -19,10 +19,28 @@ async function main() {
   const tokenSymbol = "test7";
   const tokenUri = "https://arweave.net/test-metadata";
   const imageHash = Array(32).fill(1); // Dummy hash for testing
+  const perceptualHash = new BN(0);
 
-  // Virtual reserves (Pump.fun style)
-  const virtualSolReserves = new BN("30000000000"); // 30 SOL
-  const virtualTokenReserves = new BN("800000"); // 800K tokens (pump.fun style)
+  // Starting price from the protocol's first preset, on the default curve
+  const initialReserves = { preset: [0] };
+  const curveType = { constantProduct: {} };
+  const creatorVesting = null;
+  const initialBuySol = null; // No dev-buy
+  const launchPolicy = {
+    flipTaxBps: 0,
+    flipTaxWindowSeconds: new BN(0),
+    flipTaxDestination: { rewardPool: {} },
+    allowlistRoot: Array(32).fill(0),
+    allowlistWindowSeconds: new BN(0),
+    commitWindowSlots: new BN(0),
+    revealWindowSlots: new BN(0),
+    auctionWindowSlots: new BN(0),
+    auctionTrancheBps: 0,
+    auctionMaxBidLamports: new BN(0),
+    denylistEnabled: false,
+    minBuyerBalanceLamports: new BN(0),
+    requireAttestation: false,
+  };
 
   // Find PDAs
   const [protocolPDA] = PublicKey.findProgramAddressSync(
@@ -30,11 +48,17 @@ async function main() {
     program.programId
   );
 
+  const [treasuryPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury")],
+    program.programId
+  );
+
+  // Memes are keyed by the protocol's launch counter, not the symbol
+  const protocol = await program.account.protocol.fetch(protocolPDA);
   const [memePDA] = PublicKey.findProgramAddressSync(
     [
       Buffer.from("meme"),
-      provider.wallet.publicKey.toBuffer(),
-      Buffer.from(tokenSymbol)
+      protocol.totalMemesCreated.toArrayLike(Buffer, "le", 8)
     ],
     program.programId
   );
@@ -49,6 +73,14 @@ async function main() {
     program.programId
   );
 
+  const memeSeed = (seed: string) =>
+    PublicKey.findProgramAddressSync([Buffer.from(seed), memePDA.toBuffer()], program.programId)[0];
+
+  const [imageRegistryPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("image"), Buffer.from(imageHash)],
+    program.programId
+  );
+
   const creatorTokenAccount = await getAssociatedTokenAddress(
     mintPDA,
     provider.wallet.publicKey
@@ -67,20 +99,34 @@ async function main() {
         tokenSymbol,
         tokenUri,
         imageHash,
-        virtualSolReserves,
-        virtualTokenReserves
+        perceptualHash,
+        initialReserves,
+        curveType,
+        creatorVesting,
+        initialBuySol,
+        launchPolicy
       )
-      .accounts({
+      .accountsPartial({
         protocol: protocolPDA,
         meme: memePDA,
+        imageRegistry: imageRegistryPDA,
         mint: mintPDA,
         creatorTokenAccount,
+        tokenVault: memeSeed("token_vault"),
+        creatorVesting: null,
+        vestingVault: null,
+        creatorWalletState: null,
         bondingCurveVault: vaultPDA,
+        holderStats: memeSeed("holders"),
+        rewardPool: memeSeed("reward_pool"),
+        priceOracle: memeSeed("oracle"),
+        launchBatch: null,
+        denylist: null,
         creator: provider.wallet.publicKey,
+        treasury: treasuryPDA,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
-        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       })
       .rpc();
 
@@ -11,10 +11,16 @@ async function initialize() {
   
   try {
     const tx = await program.methods
-      .initializeProtocol(100, new anchor.BN(20_000_000)) // 1% fee, 0.02 SOL creation fee
+      .initializeProtocol(
+        100, // 1% fee
+        new anchor.BN(20_000_000), // 0.02 SOL creation fee
+        new anchor.BN(85_000_000_000), // Graduates at 85 SOL
+        new anchor.BN(1_000_000_000) // Creator dev-buys up to 1 SOL
+      )
       .accounts({
+        protocol: PublicKey.findProgramAddressSync([Buffer.from("protocol")], programId)[0],
+        treasury: PublicKey.findProgramAddressSync([Buffer.from("treasury")], programId)[0],
         authority: provider.wallet.publicKey,
-        treasury: provider.wallet.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
@@ -0,0 +1,191 @@
+// ============================================================================
+// Funding Linkage
+// ============================================================================
+//
+// Groups buyers into clusters that are likely controlled by one party,
+// either for one meme or across every launch in the history. The latter is
+// how a creator builds a denylist before their own launch has any trades.
+// Two buyers are linked when
+//
+// - they were funded by the same wallet, or one funded the other, ignoring
+//   funders that fan out to so many wallets they must be an exchange or a
+//   service, or
+// - both were freshly funded right before buying, and they were funded and
+//   then bought within seconds of each other: the pattern a bundler leaves
+//   when each wallet is funded from a different source.
+//
+// Links are transitive, so a cluster is a connected group of buyers.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::history::{Funding, History};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinkageParams {
+    /// Funding this long before a wallet's first buy marks it as fresh
+    pub fresh_funding_seconds: i64,
+    /// Fresh wallets funded within this gap of each other...
+    pub funding_gap_seconds: i64,
+    /// ...whose first buys are also within this gap are linked
+    pub buy_gap_seconds: i64,
+    /// Funders of more wallets than this are never a link on their own
+    pub max_funder_fanout: usize,
+    pub min_cluster_size: usize,
+}
+
+impl Default for LinkageParams {
+    fn default() -> Self {
+        Self {
+            fresh_funding_seconds: 86_400,
+            funding_gap_seconds: 120,
+            buy_gap_seconds: 10,
+            max_funder_fanout: 500,
+            min_cluster_size: 3,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Cluster {
+    /// Sorted
+    pub wallets: Vec<String>,
+    /// Funders shared by two or more wallets of the cluster, sorted
+    pub shared_funders: Vec<String>,
+    pub sol_bought: u64,
+    pub tokens_bought: u64,
+}
+
+struct Buyer<'a> {
+    wallet: &'a str,
+    first_buy: i64,
+    sol_bought: u64,
+    tokens_bought: u64,
+    funding: Option<&'a Funding>,
+}
+
+/// Clusters of at least `params.min_cluster_size` buyers of `meme`, or of
+/// any meme when it's `None`, largest token holdings first.
+pub fn find_clusters(history: &History, meme: Option<&str>, params: &LinkageParams) -> Vec<Cluster> {
+    let mut by_wallet: BTreeMap<&str, Buyer> = BTreeMap::new();
+    let buys = history.trades.iter().filter(|t| t.is_buy && meme.is_none_or(|meme| t.meme == meme));
+    for trade in buys {
+        let buyer = by_wallet.entry(&trade.trader).or_insert(Buyer {
+            wallet: &trade.trader,
+            first_buy: trade.timestamp,
+            sol_bought: 0,
+            tokens_bought: 0,
+            funding: None,
+        });
+        buyer.first_buy = buyer.first_buy.min(trade.timestamp);
+        buyer.sol_bought = buyer.sol_bought.saturating_add(trade.sol_amount);
+        buyer.tokens_bought = buyer.tokens_bought.saturating_add(trade.token_amount);
+    }
+
+    // Each buyer's earliest funding, and how many wallets each funder funded
+    let mut fanout: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for funding in &history.fundings {
+        fanout.entry(&funding.funder).or_default().insert(&funding.wallet);
+        if let Some(buyer) = by_wallet.get_mut(funding.wallet.as_str()) {
+            if buyer.funding.is_none_or(|f| funding.timestamp < f.timestamp) {
+                buyer.funding = Some(funding);
+            }
+        }
+    }
+
+    let buyers: Vec<Buyer> = by_wallet.into_values().collect();
+    let index: HashMap<&str, usize> = buyers.iter().enumerate().map(|(i, b)| (b.wallet, i)).collect();
+    let mut sets = DisjointSets::new(buyers.len());
+
+    let mut funded_by: HashMap<&str, usize> = HashMap::new();
+    for (i, buyer) in buyers.iter().enumerate() {
+        let Some(funding) = buyer.funding else { continue };
+        let funder = funding.funder.as_str();
+        if fanout[funder].len() > params.max_funder_fanout {
+            continue;
+        }
+        if let Some(&j) = index.get(funder) {
+            sets.union(i, j);
+        }
+        if let Some(&j) = funded_by.get(funder) {
+            sets.union(i, j);
+        } else {
+            funded_by.insert(funder, i);
+        }
+    }
+
+    let mut fresh: Vec<(usize, i64, i64)> = buyers
+        .iter()
+        .enumerate()
+        .filter_map(|(i, b)| {
+            let funded_at = b.funding?.timestamp;
+            let age = b.first_buy - funded_at;
+            (0..=params.fresh_funding_seconds).contains(&age).then_some((i, funded_at, b.first_buy))
+        })
+        .collect();
+    fresh.sort_by_key(|&(_, _, first_buy)| first_buy);
+    for (k, &(i, funded_i, buy_i)) in fresh.iter().enumerate() {
+        for &(j, funded_j, buy_j) in &fresh[k + 1..] {
+            if buy_j - buy_i > params.buy_gap_seconds {
+                break;
+            }
+            if (funded_j - funded_i).abs() <= params.funding_gap_seconds {
+                sets.union(i, j);
+            }
+        }
+    }
+
+    let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for i in 0..buyers.len() {
+        groups.entry(sets.find(i)).or_default().push(i);
+    }
+    let mut clusters: Vec<Cluster> = groups
+        .into_values()
+        .filter(|members| members.len() >= params.min_cluster_size)
+        .map(|members| {
+            let mut funders: BTreeMap<&str, usize> = BTreeMap::new();
+            for &i in &members {
+                if let Some(funding) = buyers[i].funding {
+                    *funders.entry(&funding.funder).or_default() += 1;
+                }
+            }
+            Cluster {
+                wallets: members.iter().map(|&i| buyers[i].wallet.to_string()).collect(),
+                shared_funders: funders
+                    .into_iter()
+                    .filter(|&(_, count)| count > 1)
+                    .map(|(funder, _)| funder.to_string())
+                    .collect(),
+                sol_bought: members.iter().fold(0u64, |sum, &i| sum.saturating_add(buyers[i].sol_bought)),
+                tokens_bought: members.iter().fold(0u64, |sum, &i| sum.saturating_add(buyers[i].tokens_bought)),
+            }
+        })
+        .collect();
+    clusters.sort_by_key(|c| std::cmp::Reverse(c.tokens_bought));
+    clusters
+}
+
+// Union-find with path halving
+struct DisjointSets {
+    parent: Vec<usize>,
+}
+
+impl DisjointSets {
+    fn new(len: usize) -> Self {
+        Self { parent: (0..len).collect() }
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        self.parent[a.max(b)] = a.min(b);
+    }
+}
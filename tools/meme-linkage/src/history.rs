@@ -0,0 +1,45 @@
+// ============================================================================
+// Trade History
+// ============================================================================
+//
+// Indexer export the analysis runs on: curve trades as emitted in TradeEvent,
+// plus the SOL transfers that first funded each trading wallet. Wallets and
+// memes are base58 strings.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trade {
+    pub meme: String,
+    pub trader: String,
+    pub is_buy: bool,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Funding {
+    pub wallet: String,
+    pub funder: String,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    pub trades: Vec<Trade>,
+    #[serde(default)]
+    pub fundings: Vec<Funding>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        serde_json::from_str(&json).map_err(io::Error::other)
+    }
+}
@@ -0,0 +1,16 @@
+pub mod cluster;
+pub mod history;
+
+pub use cluster::{find_clusters, Cluster, LinkageParams};
+pub use history::{Funding, History, Trade};
+
+/// Wallets of every cluster of `meme`, or of any meme when it's `None`,
+/// sorted and ready for update_denylist.
+pub fn denylist(history: &History, meme: Option<&str>, params: &LinkageParams) -> Vec<String> {
+    let mut wallets: Vec<String> = find_clusters(history, meme, params)
+        .into_iter()
+        .flat_map(|cluster| cluster.wallets)
+        .collect();
+    wallets.sort();
+    wallets
+}
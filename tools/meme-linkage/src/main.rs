@@ -0,0 +1,75 @@
+use std::path::Path;
+use std::process::ExitCode;
+
+use meme_linkage::{denylist, find_clusters, History, LinkageParams};
+
+const USAGE: &str = "\
+Usage:
+  meme-linkage clusters <history.json> <meme_pubkey|all> [min_size]
+  meme-linkage denylist <history.json> <meme_pubkey|all> [min_size]
+
+`all` clusters the buyers of every launch in the history, so a creator can
+build a denylist before their own launch opens.
+`clusters` exits with status 1 when it finds a cluster of linked buyers.
+`denylist` prints their wallets as a JSON array for update_denylist.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match run(&args) {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(args: &[&str]) -> Result<ExitCode, Box<dyn std::error::Error>> {
+    let (command, history, meme, rest) = match args {
+        [command, history, meme, rest @ ..] => (*command, *history, *meme, rest),
+        _ => return usage(),
+    };
+    let mut params = LinkageParams::default();
+    match rest {
+        [] => {}
+        [size] => params.min_cluster_size = size.parse()?,
+        _ => return usage(),
+    }
+    let history = History::load(Path::new(history))?;
+    let meme = (meme != "all").then_some(meme);
+
+    match command {
+        "clusters" => {
+            let clusters = find_clusters(&history, meme, &params);
+            if clusters.is_empty() {
+                println!("no cluster of {} or more linked buyers", params.min_cluster_size);
+                return Ok(ExitCode::SUCCESS);
+            }
+            for cluster in &clusters {
+                println!(
+                    "{} wallets bought {} tokens for {} lamports (shared funders: {})",
+                    cluster.wallets.len(),
+                    cluster.tokens_bought,
+                    cluster.sol_bought,
+                    cluster.shared_funders.join(", ")
+                );
+                for wallet in &cluster.wallets {
+                    println!("  {wallet}");
+                }
+            }
+            Ok(ExitCode::FAILURE)
+        }
+        "denylist" => {
+            println!("{}", serde_json::to_string_pretty(&denylist(&history, meme, &params))?);
+            Ok(ExitCode::SUCCESS)
+        }
+        _ => usage(),
+    }
+}
+
+fn usage() -> Result<ExitCode, Box<dyn std::error::Error>> {
+    eprintln!("{USAGE}");
+    Ok(ExitCode::from(2))
+}
@@ -0,0 +1,135 @@
+use meme_linkage::{denylist, find_clusters, Funding, History, LinkageParams, Trade};
+
+const MEME: &str = "Meme111";
+
+fn buy(trader: &str, sol_amount: u64, timestamp: i64) -> Trade {
+    Trade {
+        meme: MEME.to_string(),
+        trader: trader.to_string(),
+        is_buy: true,
+        sol_amount,
+        token_amount: sol_amount * 1_000,
+        timestamp,
+    }
+}
+
+fn funding(wallet: &str, funder: &str, timestamp: i64) -> Funding {
+    Funding {
+        wallet: wallet.to_string(),
+        funder: funder.to_string(),
+        lamports: 1_000_000_000,
+        timestamp,
+    }
+}
+
+#[test]
+fn wallets_with_a_shared_funder_form_a_cluster() {
+    let history = History {
+        trades: vec![buy("a", 10, 10_000), buy("b", 20, 50_000), buy("c", 30, 90_000), buy("d", 5, 90_000)],
+        fundings: vec![
+            funding("a", "boss", 0),
+            funding("b", "boss", 1_000),
+            funding("c", "boss", 2_000),
+            funding("d", "someone", 3_000),
+        ],
+    };
+    let clusters = find_clusters(&history, Some(MEME), &LinkageParams::default());
+    assert_eq!(clusters.len(), 1);
+    assert_eq!(clusters[0].wallets, ["a", "b", "c"]);
+    assert_eq!(clusters[0].shared_funders, ["boss"]);
+    assert_eq!(clusters[0].sol_bought, 60);
+    assert_eq!(clusters[0].tokens_bought, 60_000);
+}
+
+#[test]
+fn funding_chains_through_buyers_are_transitive() {
+    // boss -> a -> b -> c, each funded long before buying
+    let history = History {
+        trades: vec![buy("a", 1, 500_000), buy("b", 1, 600_000), buy("c", 1, 700_000)],
+        fundings: vec![funding("a", "boss", 0), funding("b", "a", 1), funding("c", "b", 2)],
+    };
+    let clusters = find_clusters(&history, Some(MEME), &LinkageParams::default());
+    assert_eq!(clusters.len(), 1);
+    assert_eq!(clusters[0].wallets, ["a", "b", "c"]);
+    assert!(clusters[0].shared_funders.is_empty());
+}
+
+#[test]
+fn fresh_wallets_funded_and_buying_together_are_linked() {
+    // Different funders, but funded within a minute and buying in the same
+    // few seconds right after
+    let history = History {
+        trades: vec![buy("a", 1, 1_000), buy("b", 1, 1_003), buy("c", 1, 1_008), buy("late", 1, 1_030)],
+        fundings: vec![
+            funding("a", "cex-1", 900),
+            funding("b", "cex-2", 930),
+            funding("c", "cex-3", 960),
+            funding("late", "cex-4", 950),
+        ],
+    };
+    let clusters = find_clusters(&history, Some(MEME), &LinkageParams::default());
+    assert_eq!(clusters.len(), 1);
+    assert_eq!(clusters[0].wallets, ["a", "b", "c"]);
+
+    // Funded a day and more before, the same buys are unremarkable
+    let stale = History {
+        fundings: history
+            .fundings
+            .iter()
+            .map(|f| Funding { timestamp: f.timestamp - 200_000, ..f.clone() })
+            .collect(),
+        ..history
+    };
+    assert!(find_clusters(&stale, Some(MEME), &LinkageParams::default()).is_empty());
+}
+
+#[test]
+fn high_fanout_funders_are_ignored() {
+    let params = LinkageParams { max_funder_fanout: 5, ..LinkageParams::default() };
+    let mut fundings: Vec<Funding> = (0..10).map(|i| funding(&format!("x{i}"), "exchange", 0)).collect();
+    fundings.extend(["a", "b", "c"].map(|w| funding(w, "exchange", 0)));
+    let history = History {
+        trades: vec![buy("a", 1, 500_000), buy("b", 1, 600_000), buy("c", 1, 700_000)],
+        fundings,
+    };
+    assert!(find_clusters(&history, Some(MEME), &params).is_empty());
+}
+
+#[test]
+fn sells_other_memes_and_small_groups_are_left_out() {
+    let mut other = buy("z", 1, 10_000);
+    other.meme = "Other111".to_string();
+    let mut sell = buy("y", 1, 10_000);
+    sell.is_buy = false;
+    let history = History {
+        trades: vec![buy("a", 1, 10_000), buy("b", 1, 90_000), other, sell],
+        fundings: vec![
+            funding("a", "boss", 0),
+            funding("b", "boss", 0),
+            funding("y", "boss", 0),
+            funding("z", "boss", 0),
+        ],
+    };
+    assert!(find_clusters(&history, Some(MEME), &LinkageParams::default()).is_empty());
+
+    let pairs = LinkageParams { min_cluster_size: 2, ..LinkageParams::default() };
+    assert_eq!(denylist(&history, Some(MEME), &pairs), ["a", "b"]);
+}
+
+#[test]
+fn earlier_launches_build_a_denylist_before_any_trades() {
+    let mut other = buy("c", 1, 90_000);
+    other.meme = "Other111".to_string();
+    let whale = Trade { sol_amount: u64::MAX, ..buy("b", 1, 50_000) };
+    let history = History {
+        trades: vec![buy("a", 1, 10_000), whale, other],
+        fundings: vec![funding("a", "boss", 0), funding("b", "boss", 0), funding("c", "boss", 0)],
+    };
+    assert!(find_clusters(&history, Some("Next111"), &LinkageParams::default()).is_empty());
+
+    let clusters = find_clusters(&history, None, &LinkageParams::default());
+    assert_eq!(clusters.len(), 1);
+    assert_eq!(clusters[0].wallets, ["a", "b", "c"]);
+    assert_eq!(clusters[0].sol_bought, u64::MAX);
+    assert_eq!(denylist(&history, None, &LinkageParams::default()), ["a", "b", "c"]);
+}
@@ -0,0 +1,92 @@
+// ============================================================================
+// Perceptual Hashes
+// ============================================================================
+//
+// Both hashes fit in a u64 and survive re-encoding, resizing and small crops,
+// unlike the exact image_hash stored on-chain.
+
+use image::imageops::FilterType;
+use image::DynamicImage;
+
+const DHASH_WIDTH: u32 = 9;
+const DHASH_HEIGHT: u32 = 8;
+const PHASH_SIZE: usize = 32;
+const PHASH_LOW_FREQ: usize = 8;
+
+/// Difference hash: compares each pixel with its right neighbour on a 9x8
+/// grayscale thumbnail.
+pub fn dhash(image: &DynamicImage) -> u64 {
+    let thumb = image
+        .resize_exact(DHASH_WIDTH, DHASH_HEIGHT, FilterType::Triangle)
+        .to_luma8();
+
+    let mut hash = 0u64;
+    for y in 0..DHASH_HEIGHT {
+        for x in 0..DHASH_WIDTH - 1 {
+            let left = thumb.get_pixel(x, y)[0];
+            let right = thumb.get_pixel(x + 1, y)[0];
+            hash = (hash << 1) | u64::from(left < right);
+        }
+    }
+    hash
+}
+
+/// DCT hash: keeps the 8x8 lowest frequencies of a 32x32 grayscale DCT and
+/// sets a bit for each of the 63 AC coefficients above their median. The DC
+/// term only tracks overall brightness, so it's left out and the top bit is
+/// always 0. This is the hash stored in `MemeToken.perceptual_hash`.
+pub fn phash(image: &DynamicImage) -> u64 {
+    let thumb = image
+        .resize_exact(PHASH_SIZE as u32, PHASH_SIZE as u32, FilterType::Triangle)
+        .to_luma8();
+
+    let pixels: Vec<f64> = thumb.pixels().map(|p| f64::from(p[0])).collect();
+    let coefficients = dct_low_frequencies(&pixels);
+
+    let ac = &coefficients[1..];
+    let mut sorted = ac.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let median = sorted[sorted.len() / 2];
+
+    ac.iter()
+        .fold(0u64, |hash, &c| (hash << 1) | u64::from(c > median))
+}
+
+/// Number of differing bits between two hashes.
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+// Separable 2D DCT-II over a PHASH_SIZE square, returning only the
+// PHASH_LOW_FREQ x PHASH_LOW_FREQ top-left block in row-major order.
+fn dct_low_frequencies(pixels: &[f64]) -> Vec<f64> {
+    let n = PHASH_SIZE;
+    let basis: Vec<f64> = (0..PHASH_LOW_FREQ)
+        .flat_map(|u| {
+            (0..n).map(move |x| {
+                (std::f64::consts::PI * (2 * x + 1) as f64 * u as f64 / (2 * n) as f64).cos()
+            })
+        })
+        .collect();
+
+    // Rows first: rows[y][u]
+    let mut rows = vec![0.0; n * PHASH_LOW_FREQ];
+    for y in 0..n {
+        for u in 0..PHASH_LOW_FREQ {
+            rows[y * PHASH_LOW_FREQ + u] = (0..n)
+                .map(|x| pixels[y * n + x] * basis[u * n + x])
+                .sum();
+        }
+    }
+
+    // Then columns: out[v][u]
+    let mut out = vec![0.0; PHASH_LOW_FREQ * PHASH_LOW_FREQ];
+    for v in 0..PHASH_LOW_FREQ {
+        for u in 0..PHASH_LOW_FREQ {
+            out[v * PHASH_LOW_FREQ + u] = (0..n)
+                .map(|y| rows[y * PHASH_LOW_FREQ + u] * basis[v * n + y])
+                .sum();
+        }
+    }
+    out
+}
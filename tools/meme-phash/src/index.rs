@@ -0,0 +1,70 @@
+// ============================================================================
+// Local Launch Index
+// ============================================================================
+//
+// JSON file of perceptual hashes for memes that have already launched, used
+// by the CLI/backend to refuse near-duplicates before create_meme_token.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash::hamming_distance;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// MemeToken account (base58)
+    pub meme: String,
+    pub phash: u64,
+    pub dhash: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NearDuplicate {
+    pub entry: IndexEntry,
+    pub phash_distance: u32,
+    pub dhash_distance: u32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PhashIndex {
+    pub entries: Vec<IndexEntry>,
+}
+
+impl PhashIndex {
+    /// Loads an index, treating a missing file as empty.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).map_err(io::Error::other),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+
+    pub fn insert(&mut self, entry: IndexEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Entries whose pHash or dHash is within `max_distance` bits, closest first.
+    pub fn near_duplicates(&self, phash: u64, dhash: u64, max_distance: u32) -> Vec<NearDuplicate> {
+        let mut matches: Vec<NearDuplicate> = self
+            .entries
+            .iter()
+            .map(|entry| NearDuplicate {
+                entry: entry.clone(),
+                phash_distance: hamming_distance(entry.phash, phash),
+                dhash_distance: hamming_distance(entry.dhash, dhash),
+            })
+            .filter(|m| m.phash_distance <= max_distance || m.dhash_distance <= max_distance)
+            .collect();
+        matches.sort_by_key(|m| m.phash_distance.min(m.dhash_distance));
+        matches
+    }
+}
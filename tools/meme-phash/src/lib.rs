@@ -0,0 +1,16 @@
+pub mod hash;
+pub mod index;
+
+use std::path::Path;
+
+pub use hash::{dhash, hamming_distance, phash};
+pub use index::{IndexEntry, NearDuplicate, PhashIndex};
+
+// Hamming distance at or below which two launches are treated as the same meme
+pub const DEFAULT_MAX_DISTANCE: u32 = 10;
+
+/// Computes `(phash, dhash)` for an image file.
+pub fn hash_file(path: &Path) -> image::ImageResult<(u64, u64)> {
+    let image = image::open(path)?;
+    Ok((phash(&image), dhash(&image)))
+}
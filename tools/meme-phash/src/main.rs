@@ -0,0 +1,78 @@
+use std::path::Path;
+use std::process::ExitCode;
+
+use meme_phash::{hash_file, IndexEntry, PhashIndex, DEFAULT_MAX_DISTANCE};
+
+const USAGE: &str = "\
+Usage:
+  meme-phash hash <image>
+  meme-phash check <index.json> <image> [max_distance]
+  meme-phash add <index.json> <image> <meme_pubkey>
+
+`check` exits with status 1 when the image is a near-duplicate of an
+indexed launch; pass the printed phash to create_meme_token otherwise.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match run(&args) {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(args: &[&str]) -> Result<ExitCode, Box<dyn std::error::Error>> {
+    match args {
+        ["hash", image] => {
+            let (phash, dhash) = hash_file(Path::new(image))?;
+            println!("phash {phash:#018x} ({phash})");
+            println!("dhash {dhash:#018x} ({dhash})");
+            Ok(ExitCode::SUCCESS)
+        }
+        ["check", index, image, rest @ ..] => {
+            let max_distance = match rest {
+                [] => DEFAULT_MAX_DISTANCE,
+                [d] => d.parse()?,
+                _ => return usage(),
+            };
+            let index = PhashIndex::load(Path::new(index))?;
+            let (phash, dhash) = hash_file(Path::new(image))?;
+            let matches = index.near_duplicates(phash, dhash, max_distance);
+
+            if matches.is_empty() {
+                println!("unique: phash {phash} (no launch within {max_distance} bits)");
+                return Ok(ExitCode::SUCCESS);
+            }
+            for m in &matches {
+                println!(
+                    "near-duplicate of {} (phash distance {}, dhash distance {})",
+                    m.entry.meme, m.phash_distance, m.dhash_distance
+                );
+            }
+            Ok(ExitCode::FAILURE)
+        }
+        ["add", index_path, image, meme] => {
+            let path = Path::new(index_path);
+            let mut index = PhashIndex::load(path)?;
+            let (phash, dhash) = hash_file(Path::new(image))?;
+            index.insert(IndexEntry {
+                meme: meme.to_string(),
+                phash,
+                dhash,
+            });
+            index.save(path)?;
+            println!("indexed {meme}: phash {phash}");
+            Ok(ExitCode::SUCCESS)
+        }
+        _ => usage(),
+    }
+}
+
+fn usage() -> Result<ExitCode, Box<dyn std::error::Error>> {
+    eprintln!("{USAGE}");
+    Ok(ExitCode::from(2))
+}
@@ -0,0 +1,93 @@
+use image::imageops::FilterType;
+use image::{DynamicImage, Rgb, RgbImage};
+use meme_phash::{dhash, hamming_distance, phash, IndexEntry, PhashIndex, DEFAULT_MAX_DISTANCE};
+
+// Smooth two-blob "meme" so the hashes have real structure to latch onto
+fn meme(seed: u32) -> DynamicImage {
+    let (cx, cy) = (40.0 + (seed % 7) as f64 * 25.0, 60.0 + (seed % 5) as f64 * 30.0);
+    DynamicImage::ImageRgb8(RgbImage::from_fn(256, 256, |x, y| {
+        let d1 = ((x as f64 - cx).powi(2) + (y as f64 - cy).powi(2)).sqrt();
+        let d2 = ((x as f64 - 200.0).powi(2) + (y as f64 - (seed * 37 % 256) as f64).powi(2)).sqrt();
+        let v = (255.0 - d1.min(255.0)) * 0.6 + (255.0 - d2.min(255.0)) * 0.4;
+        Rgb([v as u8, (v * 0.8) as u8, (x ^ seed) as u8 / 4])
+    }))
+}
+
+fn reencode_jpeg(image: &DynamicImage) -> DynamicImage {
+    let mut bytes = Vec::new();
+    image
+        .write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageFormat::Jpeg)
+        .unwrap();
+    image::load_from_memory(&bytes).unwrap()
+}
+
+#[test]
+fn reencoded_resized_and_cropped_copies_stay_close() {
+    let original = meme(3);
+    let copies = [
+        reencode_jpeg(&original),
+        original.resize_exact(200, 200, FilterType::Lanczos3),
+        original.crop_imm(1, 1, 255, 255),
+    ];
+
+    for copy in &copies {
+        assert!(hamming_distance(phash(&original), phash(copy)) <= DEFAULT_MAX_DISTANCE);
+        assert!(hamming_distance(dhash(&original), dhash(copy)) <= DEFAULT_MAX_DISTANCE);
+    }
+}
+
+#[test]
+fn phash_ignores_the_dc_term() {
+    for seed in 0..8 {
+        let original = meme(seed);
+        assert_eq!(phash(&original) >> 63, 0);
+
+        // The same picture at two brightness levels differs only in the DC term
+        let shifted = |offset: u8| {
+            let rgb = original.to_rgb8();
+            DynamicImage::ImageRgb8(RgbImage::from_fn(rgb.width(), rgb.height(), |x, y| {
+                Rgb(rgb.get_pixel(x, y).0.map(|c| c / 2 + offset))
+            }))
+        };
+        assert_eq!(phash(&shifted(0)), phash(&shifted(100)));
+    }
+}
+
+#[test]
+fn index_flags_copycat_but_not_different_meme() {
+    let original = meme(3);
+    let mut index = PhashIndex::default();
+    index.insert(IndexEntry {
+        meme: "OriginalMeme1111111111111111111111111111111".to_string(),
+        phash: phash(&original),
+        dhash: dhash(&original),
+    });
+
+    let copycat = reencode_jpeg(&original.crop_imm(2, 0, 254, 256));
+    let matches = index.near_duplicates(phash(&copycat), dhash(&copycat), DEFAULT_MAX_DISTANCE);
+    assert_eq!(matches.len(), 1);
+
+    let other = DynamicImage::ImageRgb8(RgbImage::from_fn(256, 256, |x, y| {
+        let v = if (x / 32 + y / 64) % 2 == 0 { 230 } else { 20 };
+        Rgb([v, v, 255 - v])
+    }));
+    assert!(index
+        .near_duplicates(phash(&other), dhash(&other), DEFAULT_MAX_DISTANCE)
+        .is_empty());
+}
+
+#[test]
+fn index_round_trips_through_disk() {
+    let path = std::env::temp_dir().join(format!("meme-phash-index-{}.json", std::process::id()));
+    let mut index = PhashIndex::load(&path).unwrap();
+    assert!(index.entries.is_empty());
+
+    index.insert(IndexEntry {
+        meme: "Meme".to_string(),
+        phash: u64::MAX,
+        dhash: 42,
+    });
+    index.save(&path).unwrap();
+    assert_eq!(PhashIndex::load(&path).unwrap(), index);
+    std::fs::remove_file(path).unwrap();
+}